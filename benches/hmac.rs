@@ -0,0 +1,68 @@
+//!Compares `HmacKey::sign` with precomputed midstates against re-hashing padded key on each call.
+//!
+//!Run with `cargo bench --all-features --bench hmac`
+
+use lhash::{Digest, HmacKey, Sha1, Sha256, Sha512};
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 200_000;
+
+///HMAC that absorbs padded key block for both inner and outer hash on every call.
+fn sign_rehash<D: Digest>(key: &D::BlockType, input: &[u8]) -> D::OutputType {
+    let mut pad = *key;
+
+    for byte in pad.as_mut().iter_mut() {
+        *byte ^= 0x36;
+    }
+    let mut algo = D::new();
+    algo.update(pad.as_ref());
+    algo.update(input);
+    let inner_result = algo.result();
+    algo.reset();
+
+    for byte in pad.as_mut().iter_mut() {
+        *byte ^= 0x36 ^ 0x5C;
+    }
+    algo.update(pad.as_ref());
+    algo.update(inner_result.as_ref());
+    algo.result()
+}
+
+fn measure<O>(mut fun: impl FnMut() -> O) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(fun());
+    }
+    start.elapsed()
+}
+
+fn bench<D: Digest>(name: &str) {
+    let input = [0xA5u8; 64];
+    let mut key: D::BlockType = unsafe {
+        core::mem::MaybeUninit::zeroed().assume_init()
+    };
+    key.as_mut()[..32].copy_from_slice(&[0x0Bu8; 32]);
+
+    let precomputed = HmacKey::<D>::new(&[0x0Bu8; 32]);
+    assert_eq!(precomputed.sign(&input).as_ref(), sign_rehash::<D>(&key, &input).as_ref());
+
+    let rehash = measure(|| sign_rehash::<D>(black_box(&key), black_box(&input)));
+    let midstate = measure(|| black_box(&precomputed).sign(black_box(&input)));
+
+    let per_call = |elapsed: Duration| elapsed.as_nanos() / ITERATIONS as u128;
+    println!(
+        "{:<8} 64 byte input: rehash key {:>5} ns/op, midstate {:>5} ns/op, speedup {:.2}x",
+        name,
+        per_call(rehash),
+        per_call(midstate),
+        rehash.as_secs_f64() / midstate.as_secs_f64(),
+    );
+}
+
+fn main() {
+    bench::<Sha1>("sha1");
+    bench::<Sha256>("sha256");
+    bench::<Sha512>("sha512");
+}
@@ -0,0 +1,86 @@
+pub(crate) const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+pub(crate) const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+///Returns size of encoded `len` bytes.
+pub(crate) const fn encoded_len(len: usize, padding: bool) -> usize {
+    let tail = match (len % 3, padding) {
+        (0, _) => 0,
+        (_, true) => 4,
+        (rem, false) => rem + 1,
+    };
+    len / 3 * 4 + tail
+}
+
+///Returns maximum size of decoded `len` bytes.
+pub(crate) const fn decoded_len(len: usize) -> usize {
+    len / 4 * 3 + len % 4 * 3 / 4
+}
+
+///Encodes `input` into `out`, returning number of written bytes.
+///
+///Returns `None` if `out` is too small.
+pub(crate) fn encode(input: &[u8], alphabet: &[u8; 64], padding: bool, out: &mut [u8]) -> Option<usize> {
+    let len = encoded_len(input.len(), padding);
+    let out = out.get_mut(..len)?;
+
+    let mut cursor = 0;
+    for chunk in input.chunks(3) {
+        let word = (chunk[0] as u32) << 16 | (*chunk.get(1).unwrap_or(&0) as u32) << 8 | *chunk.get(2).unwrap_or(&0) as u32;
+        let chars = [
+            alphabet[(word >> 18 & 0x3f) as usize],
+            alphabet[(word >> 12 & 0x3f) as usize],
+            alphabet[(word >> 6 & 0x3f) as usize],
+            alphabet[(word & 0x3f) as usize],
+        ];
+
+        let size = match padding {
+            true => 4,
+            false => chunk.len() + 1,
+        };
+        out[cursor..cursor + size].copy_from_slice(&chars[..size]);
+        if padding {
+            for byte in out[cursor + chunk.len() + 1..cursor + 4].iter_mut() {
+                *byte = b'=';
+            }
+        }
+        cursor += size;
+    }
+
+    Some(len)
+}
+
+///Decodes `input` into `out`, returning number of written bytes.
+///
+///Padding is optional.
+///Returns `None` if `input` is not valid, has non-zero unused trailing bits, or `out` is too small.
+pub(crate) fn decode(input: &[u8], alphabet: &[u8; 64], out: &mut [u8]) -> Option<usize> {
+    let input = match input {
+        [rest @ .., b'=', b'='] if rest.len() % 4 == 2 => rest,
+        [rest @ .., b'='] if rest.len() % 4 == 3 => rest,
+        input => input,
+    };
+
+    if input.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut cursor = 0;
+    for chunk in input.chunks(4) {
+        let mut word = 0u32;
+        for byte in chunk {
+            word = word << 6 | alphabet.iter().position(|chr| chr == byte)? as u32;
+        }
+        word <<= 6 * (4 - chunk.len()) as u32;
+
+        let size = chunk.len() - 1;
+        //RFC 4648 section 3.5: pad bits of final character must be zero, otherwise encoding is not canonical
+        if word & (0xffffff >> (8 * size)) != 0 {
+            return None;
+        }
+        let bytes = word.to_be_bytes();
+        out.get_mut(cursor..cursor + size)?.copy_from_slice(&bytes[1..1 + size]);
+        cursor += size;
+    }
+
+    Some(cursor)
+}
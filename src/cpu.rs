@@ -0,0 +1,95 @@
+//!CPU feature detection for accelerated implementations.
+//!
+//!Features enabled at compile time via `target_feature` are always used.
+//!Otherwise they are detected at runtime via `CPUID` if `cpuid` feature is enabled.
+
+#[cfg(feature = "cpuid")]
+mod detect {
+    use core::arch::x86_64::{__cpuid, __cpuid_count, _xgetbv};
+    use core::sync::atomic::{AtomicU8, Ordering};
+
+    ///Marks detection as completed.
+    const INIT: u8 = 1;
+    pub(super) const SHA: u8 = 1 << 1;
+    pub(super) const SSSE3: u8 = 1 << 2;
+    pub(super) const AVX2: u8 = 1 << 3;
+
+    static FEATURES: AtomicU8 = AtomicU8::new(0);
+
+    #[target_feature(enable = "xsave")]
+    ///Reads XCR0, requires OS support of `XSAVE`.
+    unsafe fn xcr0() -> u64 {
+        _xgetbv(0)
+    }
+
+    #[allow(unused_unsafe)]
+    fn detect() -> u8 {
+        let mut features = INIT;
+        let max_leaf = unsafe { __cpuid(0) }.eax;
+        let leaf1 = unsafe { __cpuid(1) };
+
+        let ssse3 = leaf1.ecx & (1 << 9) != 0;
+        let sse41 = leaf1.ecx & (1 << 19) != 0;
+        let osxsave = leaf1.ecx & (1 << 27) != 0;
+        let avx = leaf1.ecx & (1 << 28) != 0;
+        if ssse3 {
+            features |= SSSE3;
+        }
+
+        if max_leaf >= 7 {
+            let leaf7 = unsafe { __cpuid_count(7, 0) };
+            if ssse3 && sse41 && leaf7.ebx & (1 << 29) != 0 {
+                features |= SHA;
+            }
+
+            //OS must preserve XMM and YMM registers for AVX.
+            //Safety: XGETBV is available when OSXSAVE is set.
+            if osxsave && avx && leaf7.ebx & (1 << 5) != 0 && unsafe { xcr0() } & 0b110 == 0b110 {
+                features |= AVX2;
+            }
+        }
+
+        features
+    }
+
+    ///Returns whether all of `features` are supported, detecting them on first call.
+    pub(super) fn has(features: u8) -> bool {
+        let mut detected = FEATURES.load(Ordering::Relaxed);
+        if detected == 0 {
+            detected = detect();
+            FEATURES.store(detected, Ordering::Relaxed);
+        }
+
+        detected & features == features
+    }
+}
+
+#[cfg(all(feature = "sha-ni", any(feature = "sha1", feature = "sha256")))]
+#[inline(always)]
+///Returns whether SHA extensions can be used.
+pub(crate) fn has_sha_ni() -> bool {
+    #[cfg(feature = "cpuid")]
+    return cfg!(all(target_feature = "sha", target_feature = "ssse3", target_feature = "sse4.1")) || detect::has(detect::SHA);
+    #[cfg(not(feature = "cpuid"))]
+    return cfg!(all(target_feature = "sha", target_feature = "ssse3", target_feature = "sse4.1"));
+}
+
+#[cfg(all(feature = "avx2", feature = "sha512"))]
+#[inline(always)]
+///Returns whether AVX2 can be used.
+pub(crate) fn has_avx2() -> bool {
+    #[cfg(feature = "cpuid")]
+    return cfg!(target_feature = "avx2") || detect::has(detect::AVX2);
+    #[cfg(not(feature = "cpuid"))]
+    return cfg!(target_feature = "avx2");
+}
+
+#[cfg(all(feature = "avx2", feature = "sha512"))]
+#[inline(always)]
+///Returns whether SSSE3 can be used.
+pub(crate) fn has_ssse3() -> bool {
+    #[cfg(feature = "cpuid")]
+    return cfg!(target_feature = "ssse3") || detect::has(detect::SSSE3);
+    #[cfg(not(feature = "cpuid"))]
+    return cfg!(target_feature = "ssse3");
+}
@@ -0,0 +1,449 @@
+//!crypt(3) password hashing
+//!
+//!Supports following modular crypt formats:
+//!
+//!- `$1$` - MD5-crypt by Poul-Henning Kamp (requires `md5` feature).
+//!- `$5$` - SHA-256-crypt by Ulrich Drepper (requires `sha256` feature).
+//!- `$6$` - SHA-512-crypt by Ulrich Drepper (requires `sha512` feature).
+
+use crate::Digest;
+
+use core::fmt;
+use core::num::IntErrorKind;
+
+const ALPHABET: &[u8; 64] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const MAX_OUTPUT_SIZE: usize = 128;
+
+const MD5_MAGIC: &str = "$1$";
+const MD5_SALT_SIZE: usize = 8;
+#[cfg(feature = "md5")]
+const MD5_ROUNDS: u32 = 1000;
+#[cfg(feature = "md5")]
+const MD5_ORDER: [u8; 16] = [0, 6, 12, 1, 7, 13, 2, 8, 14, 3, 9, 15, 4, 10, 5, 11];
+
+const SHA256_MAGIC: &str = "$5$";
+const SHA512_MAGIC: &str = "$6$";
+const SHA_SALT_SIZE: usize = 16;
+const ROUNDS_PREFIX: &str = "rounds=";
+///Default number of rounds for SHA-crypt.
+pub const ROUNDS_DEFAULT: u32 = 5000;
+///Minimal number of rounds for SHA-crypt.
+pub const ROUNDS_MIN: u32 = 1000;
+///Maximal number of rounds for SHA-crypt.
+pub const ROUNDS_MAX: u32 = 999_999_999;
+#[cfg(feature = "sha256")]
+const SHA256_ORDER: [u8; 32] = [
+    0, 10, 20, 21, 1, 11, 12, 22, 2, 3, 13, 23, 24, 4, 14, 15,
+    25, 5, 6, 16, 26, 27, 7, 17, 18, 28, 8, 9, 19, 29, 31, 30,
+];
+#[cfg(feature = "sha512")]
+const SHA512_ORDER: [u8; 64] = [
+    0, 21, 42, 22, 43, 1, 44, 2, 23, 3, 24, 45, 25, 46, 4, 47,
+    5, 26, 6, 27, 48, 28, 49, 7, 50, 8, 29, 9, 30, 51, 31, 52,
+    10, 53, 11, 32, 12, 33, 54, 34, 55, 13, 56, 14, 35, 15, 36, 57,
+    37, 58, 16, 59, 17, 38, 18, 39, 60, 40, 61, 19, 62, 20, 41, 63,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Modular crypt algorithm
+pub enum Algorithm {
+    ///`$1$` MD5-crypt
+    Md5,
+    ///`$5$` SHA-256-crypt
+    Sha256,
+    ///`$6$` SHA-512-crypt
+    Sha512,
+}
+
+impl Algorithm {
+    #[inline]
+    ///Returns prefix of modular crypt string.
+    pub const fn magic(&self) -> &'static str {
+        match self {
+            Algorithm::Md5 => MD5_MAGIC,
+            Algorithm::Sha256 => SHA256_MAGIC,
+            Algorithm::Sha512 => SHA512_MAGIC,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Parsed modular crypt string.
+pub struct CryptHash<'a> {
+    ///Algorithm identifier.
+    pub algorithm: Algorithm,
+    ///Number of rounds, if specified explicitly via `rounds=` parameter.
+    ///
+    ///Saturates to `u32::MAX` on overflow.
+    pub rounds: Option<u32>,
+    ///Salt, truncated to maximum salt size of algorithm.
+    pub salt: &'a str,
+    ///Encoded hash. Empty if string contains only setting.
+    pub hash: &'a str,
+}
+
+///Parses modular crypt string `$id$[rounds=N$]salt[$hash]`.
+///
+///Returns `None` if string is not in supported format.
+pub fn parse(input: &str) -> Option<CryptHash<'_>> {
+    let (algorithm, rest) = if let Some(rest) = input.strip_prefix(MD5_MAGIC) {
+        (Algorithm::Md5, rest)
+    } else if let Some(rest) = input.strip_prefix(SHA256_MAGIC) {
+        (Algorithm::Sha256, rest)
+    } else if let Some(rest) = input.strip_prefix(SHA512_MAGIC) {
+        (Algorithm::Sha512, rest)
+    } else {
+        return None;
+    };
+
+    let (rounds, rest) = match rest.strip_prefix(ROUNDS_PREFIX) {
+        Some(rounds) if algorithm != Algorithm::Md5 => {
+            let end = rounds.find('$')?;
+            //Saturate like glibc's `strtoul`, since value is clamped to `ROUNDS_MAX` anyway
+            let value = match rounds[..end].parse::<u32>() {
+                Ok(value) => value,
+                Err(error) if *error.kind() == IntErrorKind::PosOverflow => u32::MAX,
+                Err(_) => return None,
+            };
+            (Some(value), &rounds[end + 1..])
+        },
+        _ => (None, rest),
+    };
+
+    let (salt, hash) = match rest.find('$') {
+        Some(end) => (&rest[..end], &rest[end + 1..]),
+        None => (rest, ""),
+    };
+
+    let max_salt = match algorithm {
+        Algorithm::Md5 => MD5_SALT_SIZE,
+        Algorithm::Sha256 | Algorithm::Sha512 => SHA_SALT_SIZE,
+    };
+
+    if !hash.bytes().all(|byte| ALPHABET.contains(&byte)) {
+        return None;
+    }
+
+    Some(CryptHash {
+        algorithm,
+        rounds,
+        salt: truncate_salt(salt, max_salt),
+        hash,
+    })
+}
+
+fn truncate_salt(salt: &str, max: usize) -> &str {
+    let salt = match salt.find('$') {
+        Some(end) => &salt[..end],
+        None => salt,
+    };
+
+    let mut len = core::cmp::min(salt.len(), max);
+    while !salt.is_char_boundary(len) {
+        len -= 1;
+    }
+    &salt[..len]
+}
+
+///Output of crypt functions, containing full modular crypt string.
+#[derive(Clone, Copy)]
+pub struct CryptOutput {
+    buffer: [u8; MAX_OUTPUT_SIZE],
+    len: usize,
+}
+
+impl CryptOutput {
+    const fn new() -> Self {
+        Self {
+            buffer: [0; MAX_OUTPUT_SIZE],
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        self.buffer[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+    }
+
+    #[cfg(any(feature = "sha256", feature = "sha512"))]
+    fn push_rounds(&mut self, rounds: u32) {
+        let mut digits = [0u8; 10];
+        let mut cursor = digits.len();
+        let mut rounds = rounds;
+        loop {
+            cursor -= 1;
+            digits[cursor] = b'0' + (rounds % 10) as u8;
+            rounds /= 10;
+            if rounds == 0 {
+                break;
+            }
+        }
+
+        self.push(ROUNDS_PREFIX.as_bytes());
+        self.push(&digits[cursor..]);
+        self.push(b"$");
+    }
+
+    fn push_hash(&mut self, hash: &[u8], order: &[u8]) {
+        let mut groups = order.chunks_exact(3);
+
+        for group in &mut groups {
+            let word = (hash[group[0] as usize] as u32) << 16 | (hash[group[1] as usize] as u32) << 8 | hash[group[2] as usize] as u32;
+            self.push_base64(word, 4);
+        }
+
+        let word = groups.remainder().iter().fold(0u32, |word, idx| word << 8 | hash[*idx as usize] as u32);
+        self.push_base64(word, groups.remainder().len() + 1);
+    }
+
+    fn push_base64(&mut self, mut word: u32, len: usize) {
+        for _ in 0..len {
+            self.buffer[self.len] = ALPHABET[(word & 0x3f) as usize];
+            self.len += 1;
+            word >>= 6;
+        }
+    }
+
+    #[inline]
+    ///Returns modular crypt string.
+    pub fn as_str(&self) -> &str {
+        //Output consists of ASCII and salt, which is truncated at char boundary.
+        unsafe {
+            core::str::from_utf8_unchecked(&self.buffer[..self.len])
+        }
+    }
+}
+
+impl AsRef<str> for CryptOutput {
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for CryptOutput {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for CryptOutput {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), fmt)
+    }
+}
+
+impl PartialEq<str> for CryptOutput {
+    #[inline(always)]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for CryptOutput {
+    #[inline(always)]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+///Feeds `len` bytes of infinitely repeated `block` into `algo`.
+fn update_repeated<D: Digest>(algo: &mut D, block: &[u8], mut len: usize) {
+    while len > block.len() {
+        algo.update(block);
+        len -= block.len();
+    }
+    algo.update(&block[..len]);
+}
+
+#[cfg(any(feature = "sha256", feature = "sha512"))]
+fn sha_crypt<D: Digest>(magic: &str, order: &[u8], password: &[u8], salt: &str, rounds: Option<u32>) -> CryptOutput {
+    let salt = truncate_salt(salt, SHA_SALT_SIZE).as_bytes();
+    let round_num = match rounds {
+        Some(rounds) => rounds.clamp(ROUNDS_MIN, ROUNDS_MAX),
+        None => ROUNDS_DEFAULT,
+    };
+
+    let mut algo = D::new();
+    algo.update(password);
+    algo.update(salt);
+    algo.update(password);
+    let alt = algo.result();
+    algo.reset();
+    let alt = alt.as_ref();
+
+    algo.update(password);
+    algo.update(salt);
+    update_repeated(&mut algo, alt, password.len());
+    let mut len = password.len();
+    while len > 0 {
+        match len & 1 {
+            0 => algo.update(password),
+            _ => algo.update(alt),
+        }
+        len >>= 1;
+    }
+    let mut result = algo.result();
+    algo.reset();
+
+    for _ in 0..password.len() {
+        algo.update(password);
+    }
+    let password_hash = algo.result();
+    algo.reset();
+
+    for _ in 0..16 + result.as_ref()[0] as usize {
+        algo.update(salt);
+    }
+    let salt_hash = algo.result();
+    algo.reset();
+
+    let password_hash = password_hash.as_ref();
+    let salt_hash = salt_hash.as_ref();
+    for round in 0..round_num {
+        match round & 1 {
+            0 => algo.update(result.as_ref()),
+            _ => update_repeated(&mut algo, password_hash, password.len()),
+        }
+        if round % 3 != 0 {
+            update_repeated(&mut algo, salt_hash, salt.len());
+        }
+        if round % 7 != 0 {
+            update_repeated(&mut algo, password_hash, password.len());
+        }
+        match round & 1 {
+            0 => update_repeated(&mut algo, password_hash, password.len()),
+            _ => algo.update(result.as_ref()),
+        }
+        result = algo.result();
+        algo.reset();
+    }
+
+    let mut output = CryptOutput::new();
+    output.push(magic.as_bytes());
+    if rounds.is_some() {
+        output.push_rounds(round_num);
+    }
+    output.push(salt);
+    output.push(b"$");
+    output.push_hash(result.as_ref(), order);
+    output
+}
+
+#[cfg(feature = "sha256")]
+///Computes SHA-256-crypt of `password`.
+///
+///- `salt` - Salt, truncated to 16 bytes or first `$`.
+///- `rounds` - Number of rounds, clamped to `ROUNDS_MIN..=ROUNDS_MAX`. If `None`, uses `ROUNDS_DEFAULT` and omits it from output.
+pub fn sha256_crypt(password: &[u8], salt: &str, rounds: Option<u32>) -> CryptOutput {
+    sha_crypt::<crate::Sha256>(SHA256_MAGIC, &SHA256_ORDER, password, salt, rounds)
+}
+
+#[cfg(feature = "sha512")]
+///Computes SHA-512-crypt of `password`.
+///
+///- `salt` - Salt, truncated to 16 bytes or first `$`.
+///- `rounds` - Number of rounds, clamped to `ROUNDS_MIN..=ROUNDS_MAX`. If `None`, uses `ROUNDS_DEFAULT` and omits it from output.
+pub fn sha512_crypt(password: &[u8], salt: &str, rounds: Option<u32>) -> CryptOutput {
+    sha_crypt::<crate::Sha512>(SHA512_MAGIC, &SHA512_ORDER, password, salt, rounds)
+}
+
+#[cfg(feature = "md5")]
+///Computes MD5-crypt of `password`.
+///
+///`salt` is truncated to 8 bytes or first `$`.
+pub fn md5_crypt(password: &[u8], salt: &str) -> CryptOutput {
+    let salt = truncate_salt(salt, MD5_SALT_SIZE).as_bytes();
+
+    let mut algo = crate::Md5::new();
+    algo.update(password);
+    algo.update(salt);
+    algo.update(password);
+    let alt = algo.result();
+    algo.reset();
+
+    algo.update(password);
+    algo.update(MD5_MAGIC.as_bytes());
+    algo.update(salt);
+    update_repeated(&mut algo, &alt, password.len());
+    let mut len = password.len();
+    while len > 0 {
+        match len & 1 {
+            0 => algo.update(&password[..1]),
+            _ => algo.update(&[0]),
+        }
+        len >>= 1;
+    }
+    let mut result = algo.result();
+    algo.reset();
+
+    for round in 0..MD5_ROUNDS {
+        match round & 1 {
+            0 => algo.update(&result),
+            _ => algo.update(password),
+        }
+        if round % 3 != 0 {
+            algo.update(salt);
+        }
+        if round % 7 != 0 {
+            algo.update(password);
+        }
+        match round & 1 {
+            0 => algo.update(password),
+            _ => algo.update(&result),
+        }
+        result = algo.result();
+        algo.reset();
+    }
+
+    let mut output = CryptOutput::new();
+    output.push(MD5_MAGIC.as_bytes());
+    output.push(salt);
+    output.push(b"$");
+    output.push_hash(&result, &MD5_ORDER);
+    output
+}
+
+///Computes crypt of `password` using parameters from modular crypt `setting`.
+///
+///`setting` can be either full hash or only its `$id$[rounds=N$]salt` part.
+///
+///Returns `None` if `setting` is invalid or its algorithm is not enabled.
+pub fn crypt(password: &[u8], setting: &str) -> Option<CryptOutput> {
+    let setting = parse(setting)?;
+
+    match setting.algorithm {
+        #[cfg(feature = "md5")]
+        Algorithm::Md5 => Some(md5_crypt(password, setting.salt)),
+        #[cfg(feature = "sha256")]
+        Algorithm::Sha256 => Some(sha256_crypt(password, setting.salt, setting.rounds)),
+        #[cfg(feature = "sha512")]
+        Algorithm::Sha512 => Some(sha512_crypt(password, setting.salt, setting.rounds)),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
+///Verifies `password` against modular crypt `hash`.
+///
+///Comparison of hashes is performed in constant time.
+pub fn verify(password: &[u8], hash: &str) -> bool {
+    let expected = match parse(hash) {
+        Some(parsed) if !parsed.hash.is_empty() => parsed,
+        _ => return false,
+    };
+
+    let output = match crypt(password, hash) {
+        Some(output) => output,
+        None => return false,
+    };
+
+    let actual = match output.as_str().rfind('$') {
+        Some(idx) => &output.as_str()[idx + 1..],
+        None => return false,
+    };
+
+    crate::ct_eq(actual.as_bytes(), expected.hash.as_bytes())
+}
@@ -0,0 +1,273 @@
+use crate::{Digest, HmacKey};
+
+use core::fmt;
+
+///Maximum number of generate requests between reseeds, as permitted by SP 800-90A.
+const RESEED_INTERVAL: u64 = 1 << 48;
+///Maximum number of bytes per generate request (2^19 bits).
+const MAX_REQUEST_SIZE: usize = 1 << 16;
+///`seedlen` for digests with 256 bit security strength (SHA-384, SHA-512).
+const MAX_SEED_SIZE: usize = 111;
+///`seedlen` for remaining digests (SHA-1, SHA-256, SHA-512/256).
+const MIN_SEED_SIZE: usize = 55;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///DRBG error
+pub enum DrbgError {
+    ///Reseed counter exceeded reseed interval.
+    ///
+    ///Generator must be reseeded with fresh entropy before it can be used again.
+    ReseedRequired,
+    ///Requested more than 2^19 bits in a single request.
+    RequestTooLarge,
+}
+
+impl fmt::Display for DrbgError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DrbgError::ReseedRequired => fmt.write_str("DRBG reseed is required"),
+            DrbgError::RequestTooLarge => fmt.write_str("DRBG request is too large"),
+        }
+    }
+}
+
+///Adds big endian number `src` to big endian number `dst`, modulo size of `dst`.
+fn add_be(dst: &mut [u8], src: &[u8]) {
+    let mut carry = 0u16;
+    let mut src = src.iter().rev();
+
+    for byte in dst.iter_mut().rev() {
+        let sum = *byte as u16 + src.next().map_or(0, |byte| *byte as u16) + carry;
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+}
+
+///`HMAC_DRBG` as specified by NIST SP 800-90A.
+///
+///Prediction resistance is left to the user, who can reseed generator before each request.
+pub struct HmacDrbg<D: Digest> {
+    key: HmacKey<D>,
+    value: D::OutputType,
+    reseed_counter: u64,
+    reseed_interval: u64,
+}
+
+impl<D: Digest> HmacDrbg<D> {
+    ///Instantiates generator.
+    ///
+    ///- `entropy` - Entropy input, that should contain at least `security_strength` bits.
+    ///- `nonce` - Nonce, usually half of `entropy` size.
+    ///- `personalization` - Optional personalization string. Can be empty.
+    pub fn new(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Self {
+        let mut value: D::OutputType = unsafe {
+            core::mem::MaybeUninit::zeroed().assume_init()
+        };
+        let key = HmacKey::new(value.as_ref());
+        for byte in value.as_mut() {
+            *byte = 0x01;
+        }
+
+        let mut result = Self {
+            key,
+            value,
+            reseed_counter: 1,
+            reseed_interval: RESEED_INTERVAL,
+        };
+        result.update([entropy, nonce, personalization]);
+        result
+    }
+
+    ///Sets number of generate requests after which `DrbgError::ReseedRequired` is returned.
+    ///
+    ///Value is capped by 2^48, which is default interval.
+    pub fn set_reseed_interval(&mut self, interval: u64) {
+        self.reseed_interval = core::cmp::min(interval, RESEED_INTERVAL);
+    }
+
+    #[inline]
+    ///Returns current reseed counter, that is number of generate requests since last (re)seed plus one.
+    pub fn reseed_counter(&self) -> u64 {
+        self.reseed_counter
+    }
+
+    fn update(&mut self, data: [&[u8]; 3]) {
+        let key = self.key.sign_parts(&[self.value.as_ref(), &[0x00], data[0], data[1], data[2]]);
+        self.key = HmacKey::new(key.as_ref());
+        self.value = self.key.sign(self.value.as_ref());
+
+        if data.iter().any(|part| !part.is_empty()) {
+            let key = self.key.sign_parts(&[self.value.as_ref(), &[0x01], data[0], data[1], data[2]]);
+            self.key = HmacKey::new(key.as_ref());
+            self.value = self.key.sign(self.value.as_ref());
+        }
+    }
+
+    ///Reseeds generator using fresh `entropy` and optional `additional` input.
+    pub fn reseed(&mut self, entropy: &[u8], additional: &[u8]) {
+        self.update([entropy, additional, &[]]);
+        self.reseed_counter = 1;
+    }
+
+    ///Fills `out` with pseudo random bytes, using optional `additional` input.
+    pub fn generate(&mut self, additional: &[u8], out: &mut [u8]) -> Result<(), DrbgError> {
+        if out.len() > MAX_REQUEST_SIZE {
+            return Err(DrbgError::RequestTooLarge);
+        } else if self.reseed_counter > self.reseed_interval {
+            return Err(DrbgError::ReseedRequired);
+        }
+
+        if !additional.is_empty() {
+            self.update([additional, &[], &[]]);
+        }
+
+        for chunk in out.chunks_mut(self.value.as_ref().len()) {
+            self.value = self.key.sign(self.value.as_ref());
+            chunk.copy_from_slice(&self.value.as_ref()[..chunk.len()]);
+        }
+
+        self.update([additional, &[], &[]]);
+        self.reseed_counter += 1;
+
+        Ok(())
+    }
+}
+
+///Hash derivation function, filling `out` with hash of `input` parts.
+fn hash_df<D: Digest>(input: &[&[u8]], out: &mut [u8]) {
+    let bits = ((out.len() as u32) << 3).to_be_bytes();
+    let mut algo = D::new();
+    let mut counter = 1u8;
+    let mut cursor = 0;
+
+    while cursor < out.len() {
+        algo.update(&[counter]);
+        algo.update(&bits);
+        for part in input {
+            algo.update(part);
+        }
+        let hash = algo.result();
+        algo.reset();
+
+        let hash = hash.as_ref();
+        let size = core::cmp::min(hash.len(), out.len() - cursor);
+        out[cursor..cursor + size].copy_from_slice(&hash[..size]);
+
+        cursor += size;
+        counter = counter.wrapping_add(1);
+    }
+}
+
+///`Hash_DRBG` as specified by NIST SP 800-90A.
+///
+///Prediction resistance is left to the user, who can reseed generator before each request.
+pub struct HashDrbg<D: Digest> {
+    value: [u8; MAX_SEED_SIZE],
+    constant: [u8; MAX_SEED_SIZE],
+    seed_size: usize,
+    reseed_counter: u64,
+    reseed_interval: u64,
+    _digest: core::marker::PhantomData<D>,
+}
+
+impl<D: Digest> HashDrbg<D> {
+    ///Instantiates generator.
+    ///
+    ///- `entropy` - Entropy input, that should contain at least `security_strength` bits.
+    ///- `nonce` - Nonce, usually half of `entropy` size.
+    ///- `personalization` - Optional personalization string. Can be empty.
+    pub fn new(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Self {
+        //SP 800-90A Table 2 ties `seedlen` to output size, not block size: SHA-512/256 uses 440 bits.
+        let seed_size = match D::OUTPUT_SIZE > 32 {
+            true => MAX_SEED_SIZE,
+            false => MIN_SEED_SIZE,
+        };
+
+        let mut result = Self {
+            value: [0; MAX_SEED_SIZE],
+            constant: [0; MAX_SEED_SIZE],
+            seed_size,
+            reseed_counter: 1,
+            reseed_interval: RESEED_INTERVAL,
+            _digest: core::marker::PhantomData,
+        };
+        hash_df::<D>(&[entropy, nonce, personalization], &mut result.value[..seed_size]);
+        result.derive_constant();
+        result
+    }
+
+    ///Sets number of generate requests after which `DrbgError::ReseedRequired` is returned.
+    ///
+    ///Value is capped by 2^48, which is default interval.
+    pub fn set_reseed_interval(&mut self, interval: u64) {
+        self.reseed_interval = core::cmp::min(interval, RESEED_INTERVAL);
+    }
+
+    #[inline]
+    ///Returns current reseed counter, that is number of generate requests since last (re)seed plus one.
+    pub fn reseed_counter(&self) -> u64 {
+        self.reseed_counter
+    }
+
+    fn derive_constant(&mut self) {
+        let seed_size = self.seed_size;
+        hash_df::<D>(&[&[0x00], &self.value[..seed_size]], &mut self.constant[..seed_size]);
+    }
+
+    ///Reseeds generator using fresh `entropy` and optional `additional` input.
+    pub fn reseed(&mut self, entropy: &[u8], additional: &[u8]) {
+        let seed_size = self.seed_size;
+        let value = self.value;
+        hash_df::<D>(&[&[0x01], &value[..seed_size], entropy, additional], &mut self.value[..seed_size]);
+        self.derive_constant();
+        self.reseed_counter = 1;
+    }
+
+    ///Fills `out` with pseudo random bytes, using optional `additional` input.
+    pub fn generate(&mut self, additional: &[u8], out: &mut [u8]) -> Result<(), DrbgError> {
+        if out.len() > MAX_REQUEST_SIZE {
+            return Err(DrbgError::RequestTooLarge);
+        } else if self.reseed_counter > self.reseed_interval {
+            return Err(DrbgError::ReseedRequired);
+        }
+
+        let seed_size = self.seed_size;
+        let mut algo = D::new();
+
+        if !additional.is_empty() {
+            algo.update(&[0x02]);
+            algo.update(&self.value[..seed_size]);
+            algo.update(additional);
+            let hash = algo.result();
+            algo.reset();
+            add_be(&mut self.value[..seed_size], hash.as_ref());
+        }
+
+        let mut data = self.value;
+        let mut cursor = 0;
+        while cursor < out.len() {
+            algo.update(&data[..seed_size]);
+            let hash = algo.result();
+            algo.reset();
+
+            let hash = hash.as_ref();
+            let size = core::cmp::min(hash.len(), out.len() - cursor);
+            out[cursor..cursor + size].copy_from_slice(&hash[..size]);
+
+            cursor += size;
+            add_be(&mut data[..seed_size], &[0x01]);
+        }
+
+        algo.update(&[0x03]);
+        algo.update(&self.value[..seed_size]);
+        let hash = algo.result();
+        algo.reset();
+
+        add_be(&mut self.value[..seed_size], hash.as_ref());
+        add_be(&mut self.value[..seed_size], &self.constant[..seed_size]);
+        add_be(&mut self.value[..seed_size], &self.reseed_counter.to_be_bytes());
+        self.reseed_counter += 1;
+
+        Ok(())
+    }
+}
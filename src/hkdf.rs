@@ -0,0 +1,48 @@
+use crate::{Digest, HmacKey};
+
+///HKDF-Expand as specified by RFC 5869.
+///
+///- `prk` - Pseudorandom key, normally output of HKDF-Extract.
+///- `info` - Context and application specific information. Can be empty.
+///- `out` - Output keying material.
+///
+///## Panics
+///
+///If `out` is longer than 255 times `Digest::OutputType`.
+pub(crate) fn hkdf_expand<D: Digest>(prk: &[u8], info: &[u8], out: &mut [u8]) {
+    let key = HmacKey::<D>::new(prk);
+    let mut block: D::OutputType = unsafe {
+        core::mem::MaybeUninit::zeroed().assume_init()
+    };
+    let hash_len = block.as_ref().len();
+    assert!(out.len() <= 255 * hash_len, "HKDF output is too long");
+
+    let mut prev_len = 0;
+    for (idx, chunk) in out.chunks_mut(hash_len).enumerate() {
+        block = key.sign_parts(&[&block.as_ref()[..prev_len], info, &[idx as u8 + 1]]);
+        chunk.copy_from_slice(&block.as_ref()[..chunk.len()]);
+        prev_len = hash_len;
+    }
+}
+
+#[cfg(all(test, feature = "sha256"))]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+    use super::hkdf_expand;
+    use crate::{DigestFmt, Sha256};
+
+    #[test]
+    fn test_rfc5869() {
+        //RFC 5869 A.1, output spans two blocks.
+        let prk = [
+            0x07, 0x77, 0x09, 0x36, 0x2c, 0x2e, 0x32, 0xdf, 0x0d, 0xdc, 0x3f, 0x0d, 0xc4, 0x7b, 0xba, 0x63,
+            0x90, 0xb6, 0xc7, 0x3b, 0xb5, 0x0f, 0x9c, 0x31, 0x22, 0xec, 0x84, 0x4a, 0xd7, 0xc2, 0xb3, 0xe5,
+        ];
+        let info = [0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9];
+        let mut okm = [0u8; 42];
+        hkdf_expand::<Sha256>(&prk, &info, &mut okm);
+        assert_eq!(DigestFmt(&okm[..]).to_string(), "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865");
+    }
+}
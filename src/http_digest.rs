@@ -0,0 +1,714 @@
+//!HTTP Digest Access Authentication, as specified by RFC 7616.
+//!
+//!Supports following algorithms, including their `-sess` variants:
+//!
+//!- `MD5` (requires `md5` feature).
+//!- `SHA-256` (requires `sha256` feature).
+//!- `SHA-512-256` (requires `sha512_256` feature).
+//!
+//!Parameter values are kept exactly as they appear in header, i.e. with `quoted-pair` escapes.
+//!Non-ASCII user names, that require `username*` parameter, are not supported.
+
+use crate::{ct_eq, Digest};
+
+use core::fmt;
+
+///Hex size of largest supported digest output of 64 bytes.
+const MAX_HEX_SIZE: usize = 128;
+const HEX: &[u8; 16] = b"0123456789abcdef";
+const SCHEME: &str = "Digest";
+const SESS_SUFFIX: &str = "-sess";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Digest algorithm
+pub enum Algorithm {
+    ///`MD5`
+    Md5,
+    ///`SHA-256`
+    Sha256,
+    ///`SHA-512-256`
+    Sha512_256,
+}
+
+impl Algorithm {
+    #[inline]
+    ///Returns algorithm's name, as it appears in `algorithm` parameter.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Md5 => "MD5",
+            Algorithm::Sha256 => "SHA-256",
+            Algorithm::Sha512_256 => "SHA-512-256",
+        }
+    }
+
+    ///Parses `algorithm` parameter, returning algorithm and whether it is `-sess` variant.
+    fn parse(name: &str) -> Option<(Self, bool)> {
+        let (name, session) = match name.len().checked_sub(SESS_SUFFIX.len()) {
+            Some(idx) if name.is_char_boundary(idx) && name[idx..].eq_ignore_ascii_case(SESS_SUFFIX) => (&name[..idx], true),
+            _ => (name, false),
+        };
+
+        [Algorithm::Md5, Algorithm::Sha256, Algorithm::Sha512_256].iter()
+                                                                 .find(|algorithm| algorithm.name().eq_ignore_ascii_case(name))
+                                                                 .map(|algorithm| (*algorithm, session))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Quality of protection
+pub enum Qop {
+    ///`auth`, authentication only.
+    Auth,
+    ///`auth-int`, authentication with integrity protection of request body.
+    AuthInt,
+}
+
+impl Qop {
+    #[inline]
+    ///Returns name, as it appears in `qop` parameter.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Qop::Auth => "auth",
+            Qop::AuthInt => "auth-int",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("auth") {
+            Some(Qop::Auth)
+        } else if name.eq_ignore_ascii_case("auth-int") {
+            Some(Qop::AuthInt)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+///Lowercase hex encoded digest.
+pub struct HexDigest {
+    buffer: [u8; MAX_HEX_SIZE],
+    len: usize,
+}
+
+impl HexDigest {
+    fn new(hash: &[u8]) -> Self {
+        let mut buffer = [0; MAX_HEX_SIZE];
+        for (idx, byte) in hash.iter().enumerate() {
+            buffer[idx * 2] = HEX[(byte >> 4) as usize];
+            buffer[idx * 2 + 1] = HEX[(byte & 0xf) as usize];
+        }
+
+        Self {
+            buffer,
+            len: hash.len() * 2,
+        }
+    }
+
+    #[inline]
+    ///Returns hex string.
+    pub fn as_str(&self) -> &str {
+        //Only hex digits are written
+        unsafe {
+            core::str::from_utf8_unchecked(&self.buffer[..self.len])
+        }
+    }
+}
+
+impl AsRef<str> for HexDigest {
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for HexDigest {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for HexDigest {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), fmt)
+    }
+}
+
+impl PartialEq<str> for HexDigest {
+    #[inline(always)]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for HexDigest {
+    #[inline(always)]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[derive(Clone, Copy)]
+///Part of hashed value.
+enum Part<'a> {
+    ///Hashed as is.
+    Raw(&'a [u8]),
+    ///Parameter value, hashed with `quoted-pair` escapes removed.
+    Escaped(&'a str),
+}
+
+impl<'a> Part<'a> {
+    #[inline(always)]
+    fn raw(value: &'a str) -> Self {
+        Part::Raw(value.as_bytes())
+    }
+
+    fn update<D: Digest>(&self, algo: &mut D) {
+        let mut rest = match self {
+            Part::Raw(value) => return algo.update(value),
+            Part::Escaped(value) => *value,
+        };
+
+        while let Some(idx) = rest.find('\\') {
+            algo.update(&rest.as_bytes()[..idx]);
+            let escaped = &rest[idx + 1..];
+            let len = escaped.chars().next().map_or(0, char::len_utf8);
+            algo.update(&escaped.as_bytes()[..len]);
+            rest = &escaped[len..];
+        }
+        algo.update(rest.as_bytes());
+    }
+}
+
+///Hashes parts joined by `:`.
+fn hash_joined<D: Digest>(parts: &[Part<'_>]) -> HexDigest {
+    let mut algo = D::new();
+    for (idx, part) in parts.iter().enumerate() {
+        if idx > 0 {
+            algo.update(b":");
+        }
+        part.update(&mut algo);
+    }
+
+    HexDigest::new(algo.result().as_ref())
+}
+
+///Formats nonce count as 8 hex digits.
+fn format_nc(nc: u32) -> [u8; 8] {
+    let mut result = [0u8; 8];
+    for (idx, byte) in result.iter_mut().enumerate() {
+        *byte = HEX[(nc >> (28 - idx * 4) & 0xf) as usize];
+    }
+    result
+}
+
+///Computes `H(username:realm:password)`.
+pub fn ha1<D: Digest>(username: &str, realm: &str, password: &[u8]) -> HexDigest {
+    hash_joined::<D>(&[Part::raw(username), Part::raw(realm), Part::Raw(password)])
+}
+
+///Computes `-sess` variant of HA1 as `H(ha1:nonce:cnonce)` from `ha1`.
+pub fn ha1_sess<D: Digest>(ha1: &str, nonce: &str, cnonce: &str) -> HexDigest {
+    hash_joined::<D>(&[Part::raw(ha1), Part::raw(nonce), Part::raw(cnonce)])
+}
+
+///Computes HA2 as `H(method:uri)` or `H(method:uri:H(body))` when `body` is specified for `auth-int`.
+pub fn ha2<D: Digest>(method: &str, uri: &str, body: Option<&[u8]>) -> HexDigest {
+    hash_ha2::<D>(method, Part::raw(uri), body)
+}
+
+fn hash_ha2<D: Digest>(method: &str, uri: Part<'_>, body: Option<&[u8]>) -> HexDigest {
+    match body {
+        Some(body) => {
+            let body = hash_joined::<D>(&[Part::Raw(body)]);
+            hash_joined::<D>(&[Part::raw(method), uri, Part::raw(body.as_str())])
+        },
+        None => hash_joined::<D>(&[Part::raw(method), uri]),
+    }
+}
+
+///Computes `response` parameter.
+///
+///When `qop` is `None`, it is `H(ha1:nonce:ha2)` and `nc` with `cnonce` are ignored.
+///Otherwise it is `H(ha1:nonce:nc:cnonce:qop:ha2)`.
+pub fn response<D: Digest>(ha1: &str, nonce: &str, qop: Option<Qop>, nc: u32, cnonce: &str, ha2: &str) -> HexDigest {
+    hash_response::<D>(ha1, Part::raw(nonce), qop, nc, Part::raw(cnonce), ha2)
+}
+
+fn hash_response<D: Digest>(ha1: &str, nonce: Part<'_>, qop: Option<Qop>, nc: u32, cnonce: Part<'_>, ha2: &str) -> HexDigest {
+    match qop {
+        Some(qop) => hash_joined::<D>(&[Part::raw(ha1), nonce, Part::Raw(&format_nc(nc)), cnonce, Part::raw(qop.name()), Part::raw(ha2)]),
+        None => hash_joined::<D>(&[Part::raw(ha1), nonce, Part::raw(ha2)]),
+    }
+}
+
+///Computes hashed user name `H(username:realm)` for `userhash`.
+pub fn userhash<D: Digest>(username: &str, realm: &str) -> HexDigest {
+    hash_joined::<D>(&[Part::raw(username), Part::raw(realm)])
+}
+
+///Splits next `name=value` parameter from `input`, returning it along with the rest of input.
+///
+///Returns `None` if there is no more parameters.
+fn next_param(input: &str) -> Option<(&str, &str, &str)> {
+    let input = input.trim_start_matches([',', ' ', '\t']);
+    let eq = input.find('=')?;
+    let name = input[..eq].trim_end();
+    if name.is_empty() || name.contains([',', ' ', '"']) {
+        return None;
+    }
+
+    let rest = input[eq + 1..].trim_start();
+    if let Some(quoted) = rest.strip_prefix('"') {
+        let mut escaped = false;
+        for (idx, chr) in quoted.char_indices() {
+            match chr {
+                '\\' if !escaped => escaped = true,
+                '"' if !escaped => return Some((name, &quoted[..idx], &quoted[idx + 1..])),
+                _ => escaped = false,
+            }
+        }
+        None
+    } else {
+        let end = rest.find([',', ' ', '\t']).unwrap_or(rest.len());
+        Some((name, &rest[..end], &rest[end..]))
+    }
+}
+
+///Strips scheme from header value.
+fn strip_scheme(header: &str) -> Option<&str> {
+    let header = header.trim_start();
+    match header.get(..SCHEME.len()) {
+        Some(scheme) if scheme.eq_ignore_ascii_case(SCHEME) => (),
+        _ => return None,
+    }
+
+    let rest = &header[SCHEME.len()..];
+    match rest.starts_with([' ', '\t']) {
+        true => Some(rest),
+        false => None,
+    }
+}
+
+///Returns characters of `value` with `quoted-pair` escapes removed.
+fn unescape(value: &str) -> impl Iterator<Item = char> + '_ {
+    let mut escaped = false;
+    value.chars().filter(move |chr| {
+        escaped = *chr == '\\' && !escaped;
+        !escaped
+    })
+}
+
+struct Writer<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> Writer<'a> {
+    fn push(&mut self, text: &str) -> Option<()> {
+        let end = self.len + text.len();
+        self.buf.get_mut(self.len..end)?.copy_from_slice(text.as_bytes());
+        self.len = end;
+        Some(())
+    }
+
+    fn push_quoted(&mut self, value: &str) -> Option<()> {
+        self.push("\"")?;
+        self.push(value)?;
+        self.push("\"")
+    }
+
+    ///Writes quoted string, escaping `"` and `\\`.
+    fn push_escaped(&mut self, value: &str) -> Option<()> {
+        self.push("\"")?;
+        for part in value.split_inclusive(&['"', '\\'][..]) {
+            match part.strip_suffix(&['"', '\\'][..]) {
+                Some(text) => {
+                    self.push(text)?;
+                    self.push("\\")?;
+                    self.push(&part[text.len()..])?;
+                },
+                None => self.push(part)?,
+            }
+        }
+        self.push("\"")
+    }
+
+    fn into_str(self) -> &'a str {
+        //Only `str` is written
+        unsafe {
+            core::str::from_utf8_unchecked(&self.buf[..self.len])
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Parsed `WWW-Authenticate: Digest` challenge.
+pub struct Challenge<'a> {
+    ///`realm`
+    pub realm: &'a str,
+    ///Optional `domain`.
+    pub domain: Option<&'a str>,
+    ///`nonce`
+    pub nonce: &'a str,
+    ///Optional `opaque`, which must be returned unchanged.
+    pub opaque: Option<&'a str>,
+    ///Whether previous request was rejected due to stale nonce.
+    pub stale: bool,
+    ///Hash algorithm. Defaults to `MD5`.
+    pub algorithm: Algorithm,
+    ///Whether algorithm is `-sess` variant.
+    pub session: bool,
+    ///Whether server supports `qop=auth`.
+    pub qop_auth: bool,
+    ///Whether server supports `qop=auth-int`.
+    pub qop_auth_int: bool,
+    ///Whether server supports hashed user names.
+    pub userhash: bool,
+}
+
+///Parameters of request to authorize.
+pub struct Request<'a> {
+    ///User name.
+    pub username: &'a str,
+    ///Password.
+    pub password: &'a [u8],
+    ///HTTP method.
+    pub method: &'a str,
+    ///Request URI.
+    pub uri: &'a str,
+    ///Quality of protection. Must be one of offered by server, or `None` if server offers none.
+    pub qop: Option<Qop>,
+    ///Request body, which is required for `qop=auth-int`.
+    pub body: &'a [u8],
+    ///Client nonce.
+    pub cnonce: &'a str,
+    ///Nonce count, starting with 1 for each new `nonce`.
+    pub nc: u32,
+}
+
+///Parses `WWW-Authenticate` header value with `Digest` challenge.
+///
+///Returns `None` if header is not valid, or algorithm is not supported.
+pub fn parse_challenge(header: &str) -> Option<Challenge<'_>> {
+    let mut rest = strip_scheme(header)?;
+    let mut realm = None;
+    let mut nonce = None;
+    let mut result = Challenge {
+        realm: "",
+        domain: None,
+        nonce: "",
+        opaque: None,
+        stale: false,
+        algorithm: Algorithm::Md5,
+        session: false,
+        qop_auth: false,
+        qop_auth_int: false,
+        userhash: false,
+    };
+
+    while let Some((name, value, next)) = next_param(rest) {
+        rest = next;
+
+        if name.eq_ignore_ascii_case("realm") {
+            realm = Some(value);
+        } else if name.eq_ignore_ascii_case("domain") {
+            result.domain = Some(value);
+        } else if name.eq_ignore_ascii_case("nonce") {
+            nonce = Some(value);
+        } else if name.eq_ignore_ascii_case("opaque") {
+            result.opaque = Some(value);
+        } else if name.eq_ignore_ascii_case("stale") {
+            result.stale = value.eq_ignore_ascii_case("true");
+        } else if name.eq_ignore_ascii_case("algorithm") {
+            let (algorithm, session) = Algorithm::parse(value)?;
+            result.algorithm = algorithm;
+            result.session = session;
+        } else if name.eq_ignore_ascii_case("qop") {
+            for qop in value.split(',').filter_map(|qop| Qop::parse(qop.trim())) {
+                match qop {
+                    Qop::Auth => result.qop_auth = true,
+                    Qop::AuthInt => result.qop_auth_int = true,
+                }
+            }
+        } else if name.eq_ignore_ascii_case("userhash") {
+            result.userhash = value.eq_ignore_ascii_case("true");
+        }
+    }
+
+    result.realm = realm?;
+    result.nonce = nonce?;
+    Some(result)
+}
+
+///Inputs of `response`, with header values kept escaped.
+struct Params<'a> {
+    session: bool,
+    username: &'a str,
+    password: &'a [u8],
+    method: &'a str,
+    qop: Option<Qop>,
+    body: &'a [u8],
+    nc: u32,
+    realm: Part<'a>,
+    nonce: Part<'a>,
+    uri: Part<'a>,
+    cnonce: Part<'a>,
+}
+
+///Computes `response` with specified digest.
+fn compute_response<D: Digest>(params: &Params<'_>) -> HexDigest {
+    let mut ha1 = hash_joined::<D>(&[Part::raw(params.username), params.realm, Part::Raw(params.password)]);
+    if params.session {
+        ha1 = hash_joined::<D>(&[Part::raw(ha1.as_str()), params.nonce, params.cnonce]);
+    }
+    let body = match params.qop {
+        Some(Qop::AuthInt) => Some(params.body),
+        _ => None,
+    };
+    let ha2 = hash_ha2::<D>(params.method, params.uri, body);
+    hash_response::<D>(ha1.as_str(), params.nonce, params.qop, params.nc, params.cnonce, ha2.as_str())
+}
+
+fn dispatch(algorithm: Algorithm, params: &Params<'_>) -> Option<HexDigest> {
+    match algorithm {
+        #[cfg(feature = "md5")]
+        Algorithm::Md5 => Some(compute_response::<crate::Md5>(params)),
+        #[cfg(feature = "sha256")]
+        Algorithm::Sha256 => Some(compute_response::<crate::Sha256>(params)),
+        #[cfg(feature = "sha512_256")]
+        Algorithm::Sha512_256 => Some(compute_response::<crate::Sha512_256>(params)),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
+fn dispatch_userhash(algorithm: Algorithm, username: &str, realm: Part<'_>) -> Option<HexDigest> {
+    let parts = [Part::raw(username), realm];
+    match algorithm {
+        #[cfg(feature = "md5")]
+        Algorithm::Md5 => Some(hash_joined::<crate::Md5>(&parts)),
+        #[cfg(feature = "sha256")]
+        Algorithm::Sha256 => Some(hash_joined::<crate::Sha256>(&parts)),
+        #[cfg(feature = "sha512_256")]
+        Algorithm::Sha512_256 => Some(hash_joined::<crate::Sha512_256>(&parts)),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
+impl<'a> Challenge<'a> {
+    ///Writes `Authorization` header value, answering challenge, into `out`.
+    ///
+    ///User name is hashed if server supports `userhash`.
+    ///
+    ///Returns `None` if:
+    ///
+    ///- `request.qop` is not offered by server;
+    ///- `-sess` algorithm is used without `qop`;
+    ///- algorithm is not enabled;
+    ///- `out` is too small.
+    pub fn authorization<'b>(&self, request: &Request<'_>, out: &'b mut [u8]) -> Option<&'b str> {
+        match request.qop {
+            Some(Qop::Auth) if !self.qop_auth => return None,
+            Some(Qop::AuthInt) if !self.qop_auth_int => return None,
+            None if self.qop_auth || self.qop_auth_int || self.session => return None,
+            _ => (),
+        }
+
+        let params = Params {
+            session: self.session,
+            username: request.username,
+            password: request.password,
+            method: request.method,
+            qop: request.qop,
+            body: request.body,
+            nc: request.nc,
+            realm: Part::Escaped(self.realm),
+            nonce: Part::Escaped(self.nonce),
+            uri: Part::raw(request.uri),
+            cnonce: Part::raw(request.cnonce),
+        };
+        let response = dispatch(self.algorithm, &params)?;
+
+        let mut writer = Writer {
+            buf: out,
+            len: 0,
+        };
+        writer.push(SCHEME)?;
+        writer.push(" username=")?;
+        match self.userhash {
+            true => writer.push_quoted(dispatch_userhash(self.algorithm, request.username, params.realm)?.as_str())?,
+            false => writer.push_escaped(request.username)?,
+        }
+        writer.push(", realm=")?;
+        writer.push_quoted(self.realm)?;
+        writer.push(", uri=")?;
+        writer.push_escaped(request.uri)?;
+        writer.push(", algorithm=")?;
+        writer.push(self.algorithm.name())?;
+        if self.session {
+            writer.push(SESS_SUFFIX)?;
+        }
+        writer.push(", nonce=")?;
+        writer.push_quoted(self.nonce)?;
+        if let Some(qop) = request.qop {
+            writer.push(", nc=")?;
+            writer.push(unsafe { core::str::from_utf8_unchecked(&format_nc(request.nc)) })?;
+            writer.push(", cnonce=")?;
+            writer.push_escaped(request.cnonce)?;
+            writer.push(", qop=")?;
+            writer.push(qop.name())?;
+        }
+        writer.push(", response=")?;
+        writer.push_quoted(response.as_str())?;
+        if let Some(opaque) = self.opaque {
+            writer.push(", opaque=")?;
+            writer.push_quoted(opaque)?;
+        }
+        if self.userhash {
+            writer.push(", userhash=true")?;
+        }
+
+        Some(writer.into_str())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Parsed `Authorization: Digest` header.
+pub struct Authorization<'a> {
+    ///User name, or its hash if `userhash` is set.
+    pub username: &'a str,
+    ///`realm`
+    pub realm: &'a str,
+    ///`uri`
+    pub uri: &'a str,
+    ///Hash algorithm. Defaults to `MD5`.
+    pub algorithm: Algorithm,
+    ///Whether algorithm is `-sess` variant.
+    pub session: bool,
+    ///`nonce`
+    pub nonce: &'a str,
+    ///Nonce count.
+    pub nc: u32,
+    ///Client nonce.
+    pub cnonce: &'a str,
+    ///Quality of protection.
+    pub qop: Option<Qop>,
+    ///`response`
+    pub response: &'a str,
+    ///Optional `opaque`.
+    pub opaque: Option<&'a str>,
+    ///Whether user name is hashed.
+    pub userhash: bool,
+}
+
+///Parses `Authorization` header value with `Digest` credentials.
+///
+///Returns `None` if header is not valid, or algorithm is not supported.
+pub fn parse_authorization(header: &str) -> Option<Authorization<'_>> {
+    let mut rest = strip_scheme(header)?;
+    let mut username = None;
+    let mut realm = None;
+    let mut uri = None;
+    let mut nonce = None;
+    let mut response = None;
+    let mut result = Authorization {
+        username: "",
+        realm: "",
+        uri: "",
+        algorithm: Algorithm::Md5,
+        session: false,
+        nonce: "",
+        nc: 0,
+        cnonce: "",
+        qop: None,
+        response: "",
+        opaque: None,
+        userhash: false,
+    };
+
+    while let Some((name, value, next)) = next_param(rest) {
+        rest = next;
+
+        if name.eq_ignore_ascii_case("username") {
+            username = Some(value);
+        } else if name.eq_ignore_ascii_case("realm") {
+            realm = Some(value);
+        } else if name.eq_ignore_ascii_case("uri") {
+            uri = Some(value);
+        } else if name.eq_ignore_ascii_case("algorithm") {
+            let (algorithm, session) = Algorithm::parse(value)?;
+            result.algorithm = algorithm;
+            result.session = session;
+        } else if name.eq_ignore_ascii_case("nonce") {
+            nonce = Some(value);
+        } else if name.eq_ignore_ascii_case("nc") {
+            if value.len() != 8 {
+                return None;
+            }
+            result.nc = u32::from_str_radix(value, 16).ok()?;
+        } else if name.eq_ignore_ascii_case("cnonce") {
+            result.cnonce = value;
+        } else if name.eq_ignore_ascii_case("qop") {
+            result.qop = Some(Qop::parse(value)?);
+        } else if name.eq_ignore_ascii_case("response") {
+            response = Some(value);
+        } else if name.eq_ignore_ascii_case("opaque") {
+            result.opaque = Some(value);
+        } else if name.eq_ignore_ascii_case("userhash") {
+            result.userhash = value.eq_ignore_ascii_case("true");
+        }
+    }
+
+    result.username = username?;
+    result.realm = realm?;
+    result.uri = uri?;
+    result.nonce = nonce?;
+    result.response = response?;
+    Some(result)
+}
+
+impl<'a> Authorization<'a> {
+    ///Verifies `response` using user's `password`, request's `method` and `body`.
+    ///
+    ///- `username` - Actual user name, which is required when `userhash` is set.
+    ///
+    ///Server must verify `realm`, `nonce`, `nc` and `uri` separately.
+    ///Comparison of response is performed in constant time.
+    pub fn verify(&self, username: &str, password: &[u8], method: &str, body: &[u8]) -> bool {
+        if self.session && self.qop.is_none() {
+            return false;
+        }
+
+        let realm = Part::Escaped(self.realm);
+        let username_matches = match self.userhash {
+            true => matches!(dispatch_userhash(self.algorithm, username, realm), Some(hash) if hash.as_str().eq_ignore_ascii_case(self.username)),
+            false => unescape(self.username).eq(username.chars()),
+        };
+        if !username_matches {
+            return false;
+        }
+
+        let params = Params {
+            session: self.session,
+            username,
+            password,
+            method,
+            qop: self.qop,
+            body,
+            nc: self.nc,
+            realm,
+            nonce: Part::Escaped(self.nonce),
+            uri: Part::Escaped(self.uri),
+            cnonce: Part::Escaped(self.cnonce),
+        };
+
+        match dispatch(self.algorithm, &params) {
+            Some(expected) => ct_eq(expected.as_str().as_bytes(), self.response.as_bytes()),
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//!JSON Web Signature with HMAC, as specified by RFC 7515 and RFC 7518.
+//!
+//!Use `Sha256` for `HS256`, `Sha384` for `HS384` and `Sha512` for `HS512`.
+//!
+//!Only compact serialization is supported.
+//!Header is not inspected, so algorithm is always determined by digest, which prevents algorithm confusion.
+//!User is responsible for validating header and claims, like `exp`, after verification.
+
+use crate::{base64, ct_eq, Digest, HmacKey};
+
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///JWS error
+pub enum JwsError {
+    ///Token is not valid compact serialization.
+    InvalidToken,
+    ///Signature doesn't match.
+    InvalidSignature,
+    ///Output buffer is too small.
+    BufferTooSmall,
+}
+
+impl fmt::Display for JwsError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JwsError::InvalidToken => fmt.write_str("JWS is malformed"),
+            JwsError::InvalidSignature => fmt.write_str("JWS signature is invalid"),
+            JwsError::BufferTooSmall => fmt.write_str("JWS output buffer is too small"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///Verified token.
+pub struct Token<'a> {
+    ///Decoded JOSE header.
+    pub header: &'a [u8],
+    ///Decoded payload.
+    pub payload: &'a [u8],
+}
+
+///Appends base64url encoded `data` to `out` at `len`, returning new length.
+fn push_encoded(data: &[u8], out: &mut [u8], len: usize) -> Result<usize, JwsError> {
+    match out.get_mut(len..).and_then(|out| base64::encode(data, base64::URL_SAFE, false, out)) {
+        Some(size) => Ok(len + size),
+        None => Err(JwsError::BufferTooSmall),
+    }
+}
+
+///Appends `.` to `out` at `len`, returning new length.
+fn push_dot(out: &mut [u8], len: usize) -> Result<usize, JwsError> {
+    match out.get_mut(len) {
+        Some(byte) => *byte = b'.',
+        None => return Err(JwsError::BufferTooSmall),
+    }
+    Ok(len + 1)
+}
+
+///Signs `header` and `payload`, writing compact serialization into `out`.
+///
+///- `key` - HMAC key, which should be at least as long as digest output.
+///- `header` - JOSE header JSON, which should specify `alg` matching digest.
+///- `payload` - Payload, usually JWT claims JSON.
+pub fn sign<'a, D: Digest>(key: &[u8], header: &[u8], payload: &[u8], out: &'a mut [u8]) -> Result<&'a str, JwsError> {
+    let len = push_encoded(header, out, 0)?;
+    let len = push_dot(out, len)?;
+    let len = push_encoded(payload, out, len)?;
+
+    let signature = HmacKey::<D>::new(key).sign(&out[..len]);
+    let len = push_dot(out, len)?;
+    let len = push_encoded(signature.as_ref(), out, len)?;
+
+    //Only base64url and `.` are written
+    Ok(unsafe {
+        core::str::from_utf8_unchecked(&out[..len])
+    })
+}
+
+///Verifies compact serialization `token`, decoding header and payload into `out`.
+///
+///Comparison of signatures is performed in constant time.
+pub fn verify<'a, D: Digest>(key: &[u8], token: &str, out: &'a mut [u8]) -> Result<Token<'a>, JwsError> {
+    let mut parts = token.split('.');
+    let (header, payload, signature) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(header), Some(payload), Some(signature), None) => (header, payload, signature),
+        _ => return Err(JwsError::InvalidToken),
+    };
+    if token.contains('=') {
+        return Err(JwsError::InvalidToken);
+    }
+
+    let mut actual: D::OutputType = unsafe {
+        core::mem::MaybeUninit::zeroed().assume_init()
+    };
+    match base64::decode(signature.as_bytes(), base64::URL_SAFE, actual.as_mut()) {
+        Some(size) if size == actual.as_ref().len() => (),
+        _ => return Err(JwsError::InvalidToken),
+    }
+
+    let expected = HmacKey::<D>::new(key).sign(&token.as_bytes()[..header.len() + 1 + payload.len()]);
+    if !ct_eq(actual.as_ref(), expected.as_ref()) {
+        return Err(JwsError::InvalidSignature);
+    }
+
+    let header_len = decode(header, out)?;
+    let (header, rest) = out.split_at_mut(header_len);
+    let payload_len = decode(payload, rest)?;
+
+    Ok(Token {
+        header,
+        payload: &rest[..payload_len],
+    })
+}
+
+fn decode(input: &str, out: &mut [u8]) -> Result<usize, JwsError> {
+    if base64::decoded_len(input.len()) > out.len() {
+        return Err(JwsError::BufferTooSmall);
+    }
+
+    match base64::decode(input.as_bytes(), base64::URL_SAFE, out) {
+        Some(size) => Ok(size),
+        None => Err(JwsError::InvalidToken),
+    }
+}
@@ -0,0 +1,220 @@
+//!Key derivation functions
+//!
+//!- NIST SP 800-108 KBKDF in counter and feedback modes with HMAC as PRF.
+//!- NIST SP 800-56C one-step KDF with hash or HMAC as auxiliary function.
+//!- ANSI X9.63 KDF.
+//!- MGF1 mask generation function from RFC 8017.
+//!- PBKDF2 from RFC 8018.
+
+use crate::{Digest, HmacKey};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Location of counter within PRF input.
+pub enum CounterLocation {
+    ///Counter precedes everything, including iteration variable in feedback mode.
+    BeforeIter,
+    ///Counter precedes fixed input data, but follows iteration variable in feedback mode.
+    BeforeFixed,
+    ///Counter follows fixed input data.
+    AfterFixed,
+    ///Counter is inserted at specified byte offset within fixed input data.
+    MiddleFixed(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///KBKDF counter encoding.
+pub struct Counter {
+    size: usize,
+    location: CounterLocation,
+}
+
+impl Counter {
+    ///Creates new counter encoding with width in `bits` at specified `location`.
+    ///
+    ///## Panics
+    ///
+    ///If `bits` is not one of 8, 16, 24 or 32.
+    pub const fn new(bits: usize, location: CounterLocation) -> Self {
+        assert!(bits == 8 || bits == 16 || bits == 24 || bits == 32, "Counter width must be 8, 16, 24 or 32 bits");
+
+        Self {
+            size: bits / 8,
+            location,
+        }
+    }
+}
+
+impl Default for Counter {
+    #[inline]
+    ///32 bit counter before fixed input data.
+    fn default() -> Self {
+        Self::new(32, CounterLocation::BeforeFixed)
+    }
+}
+
+fn kbkdf<D: Digest>(key: &[u8], iv: Option<&[u8]>, fixed: &[u8], counter: Option<Counter>, out: &mut [u8]) {
+    let key = HmacKey::<D>::new(key);
+    let mut block: D::OutputType = unsafe {
+        core::mem::MaybeUninit::zeroed().assume_init()
+    };
+    let hash_len = block.as_ref().len();
+
+    if let Some(counter) = counter {
+        let max_blocks = (1u64 << (counter.size * 8)) - 1;
+        assert!(out.chunks(hash_len).len() as u64 <= max_blocks, "Output is too long for counter width");
+    }
+
+    let mut iter_len = 0;
+    for (idx, chunk) in out.chunks_mut(hash_len).enumerate() {
+        let counter_bytes = ((idx + 1) as u32).to_be_bytes();
+        let iter = match iv {
+            Some(iv) if idx == 0 => iv,
+            Some(_) => &block.as_ref()[..iter_len],
+            None => &[],
+        };
+
+        block = match counter {
+            Some(counter) => {
+                let counter_bytes = &counter_bytes[4 - counter.size..];
+                match counter.location {
+                    CounterLocation::BeforeIter => key.sign_parts(&[counter_bytes, iter, fixed]),
+                    CounterLocation::BeforeFixed => key.sign_parts(&[iter, counter_bytes, fixed]),
+                    CounterLocation::AfterFixed => key.sign_parts(&[iter, fixed, counter_bytes]),
+                    CounterLocation::MiddleFixed(pos) => key.sign_parts(&[iter, &fixed[..pos], counter_bytes, &fixed[pos..]]),
+                }
+            },
+            None => key.sign_parts(&[iter, fixed]),
+        };
+
+        chunk.copy_from_slice(&block.as_ref()[..chunk.len()]);
+        iter_len = hash_len;
+    }
+}
+
+///KBKDF in counter mode with HMAC, as specified by NIST SP 800-108.
+///
+///- `key` - Key derivation key `K_I`.
+///- `fixed` - Fixed input data, usually `Label || 0x00 || Context || [L]_2`.
+///- `counter` - Counter encoding.
+///- `out` - Derived keying material.
+///
+///## Panics
+///
+///- If number of output blocks cannot be represented by counter.
+///- If `CounterLocation::MiddleFixed` offset is beyond `fixed`.
+pub fn kbkdf_counter<D: Digest>(key: &[u8], fixed: &[u8], counter: Counter, out: &mut [u8]) {
+    kbkdf::<D>(key, None, fixed, Some(counter), out)
+}
+
+///KBKDF in feedback mode with HMAC, as specified by NIST SP 800-108.
+///
+///- `key` - Key derivation key `K_I`.
+///- `iv` - Initial value `K(0)`. Can be empty.
+///- `fixed` - Fixed input data, usually `Label || 0x00 || Context || [L]_2`.
+///- `counter` - Optional counter encoding.
+///- `out` - Derived keying material.
+///
+///## Panics
+///
+///- If number of output blocks cannot be represented by counter.
+///- If `CounterLocation::MiddleFixed` offset is beyond `fixed`.
+pub fn kbkdf_feedback<D: Digest>(key: &[u8], iv: &[u8], fixed: &[u8], counter: Option<Counter>, out: &mut [u8]) {
+    kbkdf::<D>(key, Some(iv), fixed, counter, out)
+}
+
+///One-step KDF with hash, as specified by NIST SP 800-56C.
+///
+///Also known as Concat KDF.
+///
+///- `secret` - Shared secret `Z`.
+///- `fixed_info` - Context specific `FixedInfo`.
+///- `out` - Derived keying material.
+pub fn one_step_kdf<D: Digest>(secret: &[u8], fixed_info: &[u8], out: &mut [u8]) {
+    hash_counter::<D>(&[], 1, &[secret, fixed_info], out)
+}
+
+///One-step KDF with HMAC, as specified by NIST SP 800-56C.
+///
+///- `salt` - HMAC key. Empty salt is equivalent to block of zeros, as default salt is.
+///- `secret` - Shared secret `Z`.
+///- `fixed_info` - Context specific `FixedInfo`.
+///- `out` - Derived keying material.
+pub fn one_step_kdf_hmac<D: Digest>(salt: &[u8], secret: &[u8], fixed_info: &[u8], out: &mut [u8]) {
+    let key = HmacKey::<D>::new(salt);
+
+    for (idx, chunk) in out.chunks_mut(D::OUTPUT_SIZE).enumerate() {
+        let counter = (idx as u32 + 1).to_be_bytes();
+        let block = key.sign_parts(&[&counter, secret, fixed_info]);
+        chunk.copy_from_slice(&block.as_ref()[..chunk.len()]);
+    }
+}
+
+///ANSI X9.63 KDF, as specified by SEC 1.
+///
+///- `secret` - Shared secret `Z`.
+///- `shared_info` - Optional `SharedInfo`. Can be empty.
+///- `out` - Derived keying material.
+pub fn x963_kdf<D: Digest>(secret: &[u8], shared_info: &[u8], out: &mut [u8]) {
+    hash_counter::<D>(secret, 1, &[shared_info], out)
+}
+
+///MGF1 mask generation function, as specified by RFC 8017.
+///
+///Fills `mask` with output derived from `seed`.
+pub fn mgf1<D: Digest>(seed: &[u8], mask: &mut [u8]) {
+    hash_counter::<D>(seed, 0, &[], mask)
+}
+
+///PBKDF2 with HMAC, as specified by RFC 8018.
+///
+///- `password` - Password.
+///- `salt` - Salt.
+///- `iterations` - Iteration count.
+///- `out` - Derived key.
+///
+///## Panics
+///
+///- If `iterations` is zero.
+pub fn pbkdf2<D: Digest>(password: &[u8], salt: &[u8], iterations: u32, out: &mut [u8]) {
+    assert!(iterations > 0, "Iteration count must be positive");
+    let key = HmacKey::<D>::new(password);
+
+    for (idx, chunk) in out.chunks_mut(D::OUTPUT_SIZE).enumerate() {
+        let counter = (idx as u32 + 1).to_be_bytes();
+        let mut block = key.sign_parts(&[salt, &counter]);
+        let mut result = block;
+
+        for _ in 1..iterations {
+            block = key.sign(block.as_ref());
+            for (byte, block_byte) in result.as_mut().iter_mut().zip(block.as_ref().iter()) {
+                *byte ^= block_byte;
+            }
+        }
+
+        chunk.copy_from_slice(&result.as_ref()[..chunk.len()]);
+    }
+}
+
+///Fills `out` with `H(prefix || counter || suffix)`, incrementing 32 bit `counter` from `start`.
+fn hash_counter<D: Digest>(prefix: &[u8], start: u32, suffix: &[&[u8]], out: &mut [u8]) {
+    let mut algo = D::new();
+    let mut counter = start;
+    let mut cursor = 0;
+
+    while cursor < out.len() {
+        algo.update(prefix);
+        algo.update(&counter.to_be_bytes());
+        for part in suffix {
+            algo.update(part);
+        }
+        let hash = algo.result();
+        algo.reset();
+
+        let hash = hash.as_ref();
+        let size = core::cmp::min(hash.len(), out.len() - cursor);
+        out[cursor..cursor + size].copy_from_slice(&hash[..size]);
+
+        cursor += size;
+        counter += 1;
+    }
+}
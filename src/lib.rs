@@ -8,8 +8,33 @@
 //!- `md5`
 //!- `sha256`
 //!- `sha512`
+//!- `sha384` (enables `sha512`)
+//!- `sha512_256` (enables `sha512`)
 //!
-//!It also includes generic hmac implementation
+//!It also includes generic constructions over any of the above:
+//!
+//!- hmac, one-shot or incremental via `Hmac`, and const `hmac_<algorithm>` functions
+//!- constant time tag comparison via `ct_eq` and `ct_eq_truncated`
+//!- `HMAC_DRBG` and `Hash_DRBG` (NIST SP 800-90A)
+//!- RFC 6979 deterministic nonces
+//!- TLS 1.0-1.3 key derivation in `tls` module
+//!- NIST SP 800-108, SP 800-56C, ANSI X9.63 KDFs, PBKDF2 and MGF1 in `kdf` module
+//!- HOTP and TOTP in `otp` module
+//!- SCRAM authentication in `scram` module
+//!- JWS HMAC signatures in `jws` module
+//!- crypt(3) MD5, SHA-256 and SHA-512 password hashing in `crypt` module
+//!- HTTP Digest authentication in `http_digest` module
+//!- AWS Signature Version 4 in `sigv4` module
+//!- GitHub, Stripe and Slack webhook signatures in `webhook` module
+//!
+//!## Hardware Acceleration
+//!
+//!- `sha-ni` - Uses x86_64 SHA extensions in `update` of `Sha1` and `Sha256`.
+//!- `avx2` - Uses AVX2, or SSSE3 if not available, for message schedule in `update` of `Sha512`, `Sha384` and `Sha512_256`.
+//!- `cpuid` - Detects accelerated instructions at runtime.
+//!
+//!Without `cpuid`, acceleration is used only when enabled at compile time via `target_feature` (e.g. `-C target-cpu=native`).
+//!const functions always use portable implementation.
 
 #![no_std]
 #![warn(missing_docs)]
@@ -17,31 +42,68 @@
 
 mod fmt;
 pub use fmt::DigestFmt;
+#[cfg(any(feature = "md5", feature = "sha1", feature = "sha256", feature = "sha512"))]
+mod state;
+#[cfg(any(feature = "md5", feature = "sha1", feature = "sha256", feature = "sha512"))]
+pub use state::StateError;
+#[cfg(all(target_arch = "x86_64", any(all(feature = "sha-ni", any(feature = "sha1", feature = "sha256")), all(feature = "avx2", feature = "sha512"))))]
+mod cpu;
+#[cfg(all(feature = "sha-ni", target_arch = "x86_64", any(feature = "sha1", feature = "sha256")))]
+mod sha_ni;
+#[cfg(all(feature = "avx2", target_arch = "x86_64", feature = "sha512"))]
+mod sha512_simd;
 
 ///Hashing algorithm interface
-pub trait Digest {
+pub trait Digest: Clone {
     ///Output type
     type OutputType: AsRef<[u8]> + AsMut<[u8]> + Copy;
     ///Block type
     type BlockType: AsRef<[u8]> + AsMut<[u8]> + Copy;
 
+    ///Size of output in bytes.
+    const OUTPUT_SIZE: usize;
+    ///Size of block in bytes.
+    const BLOCK_SIZE: usize;
+    ///Algorithm name, as used by its specification.
+    const NAME: &'static str;
+    ///DER encoded content of algorithm's ASN.1 object identifier, without tag and length.
+    const OID: Option<&'static [u8]> = None;
+
     ///Creates new instance.
     fn new() -> Self;
     ///Resets algorithm's state.
     fn reset(&mut self);
     ///Hashes input
     fn update(&mut self, input: &[u8]);
-    ///Finalizes algorithm and returns output.
-    fn result(&mut self) -> Self::OutputType;
+    ///Returns hash of all input so far, leaving state unchanged.
+    fn result(&self) -> Self::OutputType;
+
+    ///Returns hash of all input so far and resets algorithm's state.
+    fn finalize_reset(&mut self) -> Self::OutputType {
+        let result = self.result();
+        self.reset();
+        result
+    }
+
+    ///Writes hash of all input so far into `out`, leaving state unchanged.
+    ///
+    ///## Panics
+    ///
+    ///If `out` length differs from `OUTPUT_SIZE`.
+    fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(self.result().as_ref());
+    }
 }
 
+#[derive(Clone)]
 ///Represents key used to sign content in `hmac` algorithm.
 ///
 ///Comparing to `hmac` function it allows to pre-compute key and just sign input directly.
 ///
-///`Digest` is only used in methods, making user to be responsible for using correct algorithm.
+///Stores `Digest` states after absorbing padded key, so signing only processes input and inner digest.
 pub struct HmacKey<D: Digest> {
-    key: D::BlockType,
+    inner: D,
+    outer: D,
 }
 
 impl<D: Digest> HmacKey<D> {
@@ -50,51 +112,208 @@ impl<D: Digest> HmacKey<D> {
     ///If `secret` size is above that of `Digest::BlockType` then it is hashed,
     ///reducing potential quality of hmac properties.
     pub fn new(secret: &[u8]) -> Self {
-        let mut inner: D::BlockType = unsafe {
+        let mut block: D::BlockType = unsafe {
             core::mem::MaybeUninit::zeroed().assume_init()
         };
-        let key = inner.as_mut();
+        let key = block.as_mut();
 
         if secret.len() <= key.len() {
             key[..secret.len()].copy_from_slice(secret);
         } else {
-        let mut algo = D::new();
+            let mut algo = D::new();
             algo.update(secret);
             let hash = algo.result();
             let hash = hash.as_ref();
             key[..hash.len()].copy_from_slice(hash);
-            algo.reset();
         }
 
         for byte in key.iter_mut() {
             *byte ^= 0x36;
         }
+        let mut inner = D::new();
+        inner.update(key);
+
+        for byte in key.iter_mut() {
+            *byte ^= 0x36 ^ 0x5C;
+        }
+        let mut outer = D::new();
+        outer.update(key);
 
         Self {
-            key: inner,
+            inner,
+            outer,
         }
     }
 
     ///Signs provided `input` with the key.
     pub fn sign(&self, input: &[u8]) -> D::OutputType {
-        let mut key = self.key;
-        let key = key.as_mut();
-
-        //inner
-        let mut algo = D::new();
-        algo.update(key);
-        algo.update(input);
-        let inner_result = algo.result();
-        algo.reset();
+        self.sign_parts(&[input])
+    }
 
-        //outer
-        for byte in key.iter_mut() {
-            *byte ^= 0x36 ^ 0x5C;
+    ///Signs concatenation of `parts` with the key.
+    pub(crate) fn sign_parts(&self, parts: &[&[u8]]) -> D::OutputType {
+        let mut algo = self.inner.clone();
+        for part in parts {
+            algo.update(part);
         }
-        algo.update(key);
+        self.finish(&algo)
+    }
+
+    ///Finalizes `inner` hash and returns outer hash over its result.
+    fn finish(&self, inner: &D) -> D::OutputType {
+        let inner_result = inner.result();
+        let mut algo = self.outer.clone();
         algo.update(inner_result.as_ref());
         algo.result()
     }
+
+    ///Signs `input` and compares result with `tag` in constant time.
+    ///
+    ///`tag` must be of full output size.
+    pub fn verify(&self, input: &[u8], tag: &[u8]) -> bool {
+        ct_eq(self.sign(input).as_ref(), tag)
+    }
+
+    ///Signs `input` and compares result with truncated `tag` in constant time.
+    ///
+    ///`tag` must be at least `min_len` bytes, see `ct_eq_truncated`.
+    pub fn verify_truncated(&self, input: &[u8], tag: &[u8], min_len: usize) -> bool {
+        ct_eq_truncated(self.sign(input).as_ref(), tag, min_len)
+    }
+
+    ///Creates incremental `Hmac` instance with the key.
+    pub fn hmac(&self) -> Hmac<D> {
+        Hmac::from_key(self)
+    }
+}
+
+#[derive(Clone)]
+///Incremental HMAC algorithm.
+///
+///Implements `Digest` itself, allowing to use it in generic code.
+///`Digest::new` creates instance with empty key.
+pub struct Hmac<D: Digest> {
+    key: HmacKey<D>,
+    algo: D,
+}
+
+impl<D: Digest> Hmac<D> {
+    ///Creates new instance from pre-computed `key`.
+    pub fn from_key(key: &HmacKey<D>) -> Self {
+        Self {
+            key: key.clone(),
+            algo: key.inner.clone(),
+        }
+    }
+
+    ///Creates new instance, deriving key from `secret` as `HmacKey::new` does.
+    pub fn new(secret: &[u8]) -> Self {
+        let key = HmacKey::<D>::new(secret);
+        Self {
+            algo: key.inner.clone(),
+            key,
+        }
+    }
+
+    ///Resets algorithm's state, keeping the key.
+    pub fn reset(&mut self) {
+        self.algo = self.key.inner.clone();
+    }
+
+    ///Hashes input
+    pub fn update(&mut self, input: &[u8]) {
+        self.algo.update(input);
+    }
+
+    ///Returns HMAC of all input so far, leaving state unchanged.
+    pub fn result(&self) -> D::OutputType {
+        self.key.finish(&self.algo)
+    }
+
+    ///Returns HMAC of all input so far and resets algorithm's state, keeping the key.
+    pub fn finalize_reset(&mut self) -> D::OutputType {
+        let result = self.result();
+        self.reset();
+        result
+    }
+
+    ///Writes HMAC of all input so far into `out`, leaving state unchanged.
+    ///
+    ///## Panics
+    ///
+    ///If `out` length differs from output size.
+    pub fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(self.result().as_ref());
+    }
+
+    ///Compares HMAC of all input so far with `tag` in constant time.
+    ///
+    ///`tag` must be of full output size.
+    pub fn verify(&self, tag: &[u8]) -> bool {
+        let result = self.result();
+        ct_eq(result.as_ref(), tag)
+    }
+
+    ///Compares HMAC of all input so far with truncated `tag` in constant time.
+    ///
+    ///`tag` must be at least `min_len` bytes, see `ct_eq_truncated`.
+    pub fn verify_truncated(&self, tag: &[u8], min_len: usize) -> bool {
+        let result = self.result();
+        ct_eq_truncated(result.as_ref(), tag, min_len)
+    }
+}
+
+impl<D: Digest> Digest for Hmac<D> {
+    type OutputType = D::OutputType;
+    type BlockType = D::BlockType;
+
+    const OUTPUT_SIZE: usize = D::OUTPUT_SIZE;
+    const BLOCK_SIZE: usize = D::BLOCK_SIZE;
+    const NAME: &'static str = "HMAC";
+
+    #[inline(always)]
+    fn new() -> Self {
+        Self::new(&[])
+    }
+
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    #[inline(always)]
+    fn update(&mut self, input: &[u8]) {
+        self.update(input);
+    }
+
+    #[inline(always)]
+    fn result(&self) -> Self::OutputType {
+        self.result()
+    }
+
+    #[inline(always)]
+    fn finalize_reset(&mut self) -> Self::OutputType {
+        self.finalize_reset()
+    }
+
+    #[inline(always)]
+    fn finalize_into(&self, out: &mut [u8]) {
+        self.finalize_into(out);
+    }
+}
+
+impl<D: Digest> core::fmt::Debug for HmacKey<D> {
+    ///Writes only algorithm name, without key material.
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.debug_struct("HmacKey").field("algorithm", &D::NAME).finish_non_exhaustive()
+    }
+}
+
+impl<D: Digest> core::fmt::Debug for Hmac<D> {
+    ///Writes only algorithm name, without key material or state.
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.debug_struct("Hmac").field("algorithm", &D::NAME).finish_non_exhaustive()
+    }
 }
 
 ///Creates HMAC using provided `Digest` algorithm.
@@ -106,21 +325,98 @@ pub fn hmac<D: Digest>(input: &[u8], secret: &[u8]) -> D::OutputType {
     key.sign(input)
 }
 
+#[cfg(any(feature = "md5", feature = "sha1", feature = "sha256", feature = "sha512"))]
+///Derives inner and outer pads of `BLOCK` bytes from `key`, used by const `hmac_<algorithm>` functions.
+///
+///`key` must be already reduced to at most `BLOCK` bytes.
+const fn hmac_pads<const BLOCK: usize>(key: &[u8]) -> ([u8; BLOCK], [u8; BLOCK]) {
+    let mut inner_key = [0x36u8; BLOCK];
+    let mut outer_key = [0x5Cu8; BLOCK];
+    let mut idx = 0;
+    while idx < key.len() {
+        inner_key[idx] ^= key[idx];
+        outer_key[idx] ^= key[idx];
+        idx += 1;
+    }
+    (inner_key, outer_key)
+}
+
+///Compares byte slices in constant time, leaking only their lengths.
+///
+///Difference is accumulated through volatile reads, so optimizer cannot stop at first mismatch.
+pub fn ct_eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut diff = 0u8;
+    for (left, right) in left.iter().zip(right.iter()) {
+        diff |= left ^ right;
+        diff = unsafe {
+            core::ptr::read_volatile(&diff)
+        };
+    }
+    diff == 0
+}
+
+///Compares truncated `tag` with prefix of `expected` in constant time.
+///
+///Returns `false` if `tag` is shorter than `min_len` or longer than `expected`.
+///
+///## Panics
+///
+///If `min_len` is zero, as empty tag would match anything.
+pub fn ct_eq_truncated(expected: &[u8], tag: &[u8], min_len: usize) -> bool {
+    assert!(min_len > 0, "Minimum tag length must be above zero");
+
+    tag.len() >= min_len && tag.len() <= expected.len() && ct_eq(&expected[..tag.len()], tag)
+}
+
+mod base64;
+mod drbg;
+pub use drbg::{DrbgError, HmacDrbg, HashDrbg};
+mod hkdf;
+mod rfc6979;
+pub use rfc6979::{rfc6979, Rfc6979, Rfc6979Nonce};
+pub mod tls;
+pub mod kdf;
+pub mod otp;
+pub mod scram;
+pub mod jws;
+#[cfg(any(feature = "md5", feature = "sha256", feature = "sha512"))]
+pub mod crypt;
+#[cfg(any(feature = "md5", feature = "sha256", feature = "sha512_256"))]
+pub mod http_digest;
+#[cfg(feature = "sha256")]
+pub mod sigv4;
+#[cfg(feature = "sha256")]
+pub mod webhook;
+
 #[cfg(feature = "sha1")]
 mod sha1;
 #[cfg(feature = "sha1")]
-pub use sha1::{sha1, Sha1};
+pub use sha1::{sha1, hmac_sha1, Sha1, Sha1State};
 #[cfg(feature = "md5")]
 mod md5;
 #[cfg(feature = "md5")]
-pub use md5::{md5, Md5};
+pub use md5::{md5, hmac_md5, Md5, Md5State};
 
 #[cfg(feature = "sha256")]
 mod sha256;
 #[cfg(feature = "sha256")]
-pub use sha256::{sha256, Sha256};
+pub use sha256::{sha256, hmac_sha256, Sha256, Sha256State};
 
 #[cfg(feature = "sha512")]
 mod sha512;
 #[cfg(feature = "sha512")]
-pub use sha512::{sha512, Sha512};
+pub use sha512::{sha512, hmac_sha512, Sha512, Sha512State};
+
+#[cfg(feature = "sha384")]
+mod sha384;
+#[cfg(feature = "sha384")]
+pub use sha384::{sha384, Sha384};
+
+#[cfg(feature = "sha512_256")]
+mod sha512_256;
+#[cfg(feature = "sha512_256")]
+pub use sha512_256::{sha512_256, Sha512_256};
@@ -1,5 +1,11 @@
+use crate::state::{self, StateError};
+
+use core::fmt;
+
 const BLOCK_SIZE: usize = 64;
 const STATE_SIZE: usize = 4;
+///Size of exported state.
+const EXPORT_SIZE: usize = state::HEADER_SIZE + 8 + STATE_SIZE * 4 + BLOCK_SIZE;
 const RESULT_SIZE: usize = 16;
 const INIT_STATE: [u32; STATE_SIZE] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
 
@@ -228,7 +234,73 @@ pub const fn md5(input: &[u8]) -> [u8; 16] {
     ]
 }
 
+///const `HMAC-MD5` algorithm implementation
+///
+///- `input` - Data to hash.
+///- `secret` - Data to derive HMAC's key.
+pub const fn hmac_md5(input: &[u8], secret: &[u8]) -> [u8; 16] {
+    let (inner_key, outer_key) = match secret.len() <= BLOCK_SIZE {
+        true => crate::hmac_pads::<BLOCK_SIZE>(secret),
+        false => crate::hmac_pads::<BLOCK_SIZE>(&md5(secret)),
+    };
+
+    let inner_result = Md5::new().const_update(&inner_key).const_update(input).const_result();
+    Md5::new().const_update(&outer_key).const_update(&inner_result).const_result()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Raw `MD5` state for low-level compression.
+///
+///No padding or length is applied, allowing custom initial values and padding schemes.
+pub struct Md5State(pub [u32; STATE_SIZE]);
+
+impl Md5State {
+    ///Creates state with standard initial value.
+    pub const fn new() -> Self {
+        Self(INIT_STATE)
+    }
+
+    ///Compresses single `block`.
+    pub const fn compress(self, block: &[u8; BLOCK_SIZE]) -> Self {
+        Self(md5_transform(self.0, 0, block))
+    }
+
+    ///Compresses `blocks` in order.
+    pub fn compress_blocks(&mut self, blocks: &[[u8; BLOCK_SIZE]]) {
+        for block in blocks {
+            self.0 = md5_transform(self.0, 0, block);
+        }
+    }
+
+    ///Serializes state words in little endian, as in digest output.
+    pub const fn to_bytes(&self) -> [u8; STATE_SIZE * 4] {
+        let mut out = [0u8; STATE_SIZE * 4];
+        let mut idx = 0;
+        while idx < STATE_SIZE {
+            let word = self.0[idx].to_le_bytes();
+            let mut byte = 0;
+            while byte < 4 {
+                out[idx * 4 + byte] = word[byte];
+                byte += 1;
+            }
+            idx += 1;
+        }
+        out
+    }
+}
+
+impl Default for Md5State {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 ///`MD5` algorithm implementation
+///
+///Unlike SHA family, which panics once input reaches 2^64 bits, `MD5` deliberately wraps length modulo 2^64 bits
+///as RFC 1321 specifies, so it never panics on long input.
+#[derive(Clone, Copy)]
 pub struct Md5 {
     state: [u32; STATE_SIZE],
     len: u64,
@@ -254,9 +326,11 @@ impl Md5 {
     }
 
     ///Hashes input
+    ///
+    ///Length of input is unlimited, as RFC 1321 specifies it modulo 2^64 bits.
     pub const fn const_update(mut self, input: &[u8]) -> Self {
         let num = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
-        self.len += input.len() as u64;
+        self.len = self.len.wrapping_add(input.len() as u64);
 
         let mut cursor = 0;
 
@@ -297,9 +371,11 @@ impl Md5 {
     }
 
     ///Hashes input
+    ///
+    ///Length of input is unlimited, as RFC 1321 specifies it modulo 2^64 bits.
     pub fn update(&mut self, input: &[u8]) {
         let mut num = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
-        self.len += input.len() as u64;
+        self.len = self.len.wrapping_add(input.len() as u64);
 
         let mut cursor = 0;
 
@@ -328,6 +404,24 @@ impl Md5 {
         }
     }
 
+    ///Hashes whole `blocks` directly, bypassing internal buffering.
+    ///
+    ///If previous input left incomplete block in buffer, `blocks` are buffered as by `update`.
+    pub fn compress_blocks(&mut self, blocks: &[[u8; BLOCK_SIZE]]) {
+        if self.len & (BLOCK_SIZE as u64 - 1) != 0 {
+            for block in blocks {
+                self.update(block);
+            }
+            return;
+        }
+
+
+        for block in blocks {
+            self.state = md5_transform(self.state, 0, block);
+        }
+        self.len = self.len.wrapping_add(blocks.len() as u64 * BLOCK_SIZE as u64);
+    }
+
     ///Finalizes algorithm and returns output.
     pub const fn const_result(mut self) -> [u8; Self::RESULT_SIZE] {
         let mut pos = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
@@ -370,46 +464,67 @@ impl Md5 {
         ]
     }
 
-    ///Finalizes algorithm and returns output.
-    pub fn result(&mut self) -> [u8; Self::RESULT_SIZE] {
-        let mut pos = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
+    ///Returns hash of all input so far, leaving state unchanged.
+    ///
+    ///More input can be added afterwards, extending already hashed data.
+    pub fn result(&self) -> [u8; Self::RESULT_SIZE] {
+        (*self).const_result()
+    }
 
-        self.buffer[pos] = 0x80;
-        pos += 1;
+    ///Returns hash of all input so far and resets algorithm's state.
+    pub fn finalize_reset(&mut self) -> [u8; Self::RESULT_SIZE] {
+        let result = self.result();
+        self.reset();
+        result
+    }
 
-        while pos != (BLOCK_SIZE - core::mem::size_of::<u64>()) {
-            pos &= BLOCK_SIZE - 1;
+    ///Writes hash of all input so far into `out`, leaving state unchanged.
+    ///
+    ///## Panics
+    ///
+    ///If `out` length differs from output size.
+    pub fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.result());
+    }
 
-            if pos == 0 {
-                self.state = md5_transform(self.state, 0, &self.buffer);
-            }
+    ///Exports current state, allowing to resume hashing later via `import_state`.
+    ///
+    ///Buffered input is included, but no other input can be recovered from it.
+    pub fn export_state(&self) -> [u8; EXPORT_SIZE] {
+        let mut out = [0u8; EXPORT_SIZE];
+        state::write_header(&mut out, state::MD5);
 
-            self.buffer[pos] = 0;
-            pos += 1;
+        let (len, rest) = out[state::HEADER_SIZE..].split_at_mut(8);
+        len.copy_from_slice(&self.len.to_be_bytes());
+
+        let (words, buffer) = rest.split_at_mut(STATE_SIZE * 4);
+        for (chunk, word) in words.chunks_mut(4).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
         }
 
-        let len = self.len.wrapping_shl(3).to_le_bytes();
-        self.buffer[pos] = len[0];
-        self.buffer[pos + 1] = len[1];
-        self.buffer[pos + 2] = len[2];
-        self.buffer[pos + 3] = len[3];
-        self.buffer[pos + 4] = len[4];
-        self.buffer[pos + 5] = len[5];
-        self.buffer[pos + 6] = len[6];
-        self.buffer[pos + 7] = len[7];
+        let tail = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
+        buffer[..tail].copy_from_slice(&self.buffer[..tail]);
+        out
+    }
 
-        self.state = md5_transform(self.state, 0, &self.buffer);
+    ///Restores state, previously exported by `export_state`.
+    pub fn import_state(input: &[u8]) -> Result<Self, StateError> {
+        state::check_header(input, EXPORT_SIZE, state::MD5)?;
 
-        let a = self.state[0].to_le_bytes();
-        let b = self.state[1].to_le_bytes();
-        let c = self.state[2].to_le_bytes();
-        let d = self.state[3].to_le_bytes();
-        [
-            a[0], a[1], a[2], a[3],
-            b[0], b[1], b[2], b[3],
-            c[0], c[1], c[2], c[3],
-            d[0], d[1], d[2], d[3],
-        ]
+        let (len, rest) = input[state::HEADER_SIZE..].split_at(8);
+        let mut result = Self::new();
+        let mut len_bytes = [0u8; 8];
+        len_bytes.copy_from_slice(len);
+        result.len = u64::from_be_bytes(len_bytes);
+
+        let (words, buffer) = rest.split_at(STATE_SIZE * 4);
+        for (word, chunk) in result.state.iter_mut().zip(words.chunks(4)) {
+            *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+
+        let tail = (result.len & (BLOCK_SIZE as u64 - 1)) as usize;
+        result.buffer[..tail].copy_from_slice(&buffer[..tail]);
+        Ok(result)
     }
 }
 
@@ -417,6 +532,11 @@ impl super::Digest for Md5 {
     type OutputType = [u8; Self::RESULT_SIZE];
     type BlockType = [u8; BLOCK_SIZE];
 
+    const OUTPUT_SIZE: usize = Self::RESULT_SIZE;
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+    const NAME: &'static str = "MD5";
+    const OID: Option<&'static [u8]> = Some(&[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x02, 0x05]);
+
     #[inline(always)]
     fn new() -> Self {
         Self::new()
@@ -433,7 +553,31 @@ impl super::Digest for Md5 {
     }
 
     #[inline(always)]
-    fn result(&mut self) -> Self::OutputType {
+    fn result(&self) -> Self::OutputType {
         self.result()
     }
+
+    #[inline(always)]
+    fn finalize_reset(&mut self) -> Self::OutputType {
+        self.finalize_reset()
+    }
+
+    #[inline(always)]
+    fn finalize_into(&self, out: &mut [u8]) {
+        self.finalize_into(out);
+    }
+}
+
+impl Default for Md5 {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Md5 {
+    ///Writes only number of hashed bytes, without state or buffered input.
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Md5").field("len", &self.len).finish_non_exhaustive()
+    }
 }
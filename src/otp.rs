@@ -0,0 +1,114 @@
+//!One-time password algorithms
+//!
+//!- HOTP as specified by RFC 4226.
+//!- TOTP as specified by RFC 6238.
+//!
+//!Codes are returned as integers, and should be formatted with leading zeros up to `digits`.
+//!Dynamic truncation requires digest output of at least 20 bytes, so `MD5` cannot be used.
+
+use crate::{Digest, HmacKey};
+
+///Returns `10^digits`.
+///
+///## Panics
+///
+///- If `digits` is not within `1..=9`.
+///- If digest output is shorter than 20 bytes.
+fn modulus<D: Digest>(digits: u32) -> u32 {
+    assert!(D::OUTPUT_SIZE >= 20, "Digest output must be at least 20 bytes");
+    assert!(digits > 0 && digits <= 9, "Number of digits must be within 1..=9");
+    10u32.pow(digits)
+}
+
+fn truncate<D: Digest>(key: &HmacKey<D>, counter: u64, modulus: u32) -> u32 {
+    let hash = key.sign(&counter.to_be_bytes());
+    let hash = hash.as_ref();
+    let offset = (hash[hash.len() - 1] & 0xf) as usize;
+    let code = u32::from_be_bytes([hash[offset] & 0x7f, hash[offset + 1], hash[offset + 2], hash[offset + 3]]);
+
+    code % modulus
+}
+
+///Generates HOTP code.
+///
+///- `secret` - Shared secret.
+///- `counter` - Moving factor.
+///- `digits` - Number of digits in code, usually 6.
+///
+///## Panics
+///
+///- If `digits` is not within `1..=9`.
+///- If digest output is shorter than 20 bytes.
+pub fn hotp<D: Digest>(secret: &[u8], counter: u64, digits: u32) -> u32 {
+    truncate(&HmacKey::<D>::new(secret), counter, modulus::<D>(digits))
+}
+
+///Verifies HOTP `code` within look-ahead `window` after `counter`.
+///
+///Returns counter value that matched `code`.
+///User should store next value after it to prevent replay and re-synchronize with token.
+///
+///## Panics
+///
+///- If `digits` is not within `1..=9`.
+///- If digest output is shorter than 20 bytes.
+pub fn verify_hotp<D: Digest>(secret: &[u8], counter: u64, digits: u32, window: u64, code: u32) -> Option<u64> {
+    let key = HmacKey::<D>::new(secret);
+    let modulus = modulus::<D>(digits);
+
+    let mut idx = 0;
+    while idx <= window {
+        let counter = counter.checked_add(idx)?;
+        if truncate(&key, counter, modulus) == code {
+            return Some(counter);
+        }
+        idx += 1;
+    }
+
+    None
+}
+
+#[inline]
+///Returns TOTP time step counter for `unix_time`.
+///
+///## Panics
+///
+///If `step` is zero.
+pub fn time_step(unix_time: u64, step: u64, t0: u64) -> u64 {
+    unix_time.saturating_sub(t0) / step
+}
+
+///Generates TOTP code.
+///
+///- `secret` - Shared secret.
+///- `unix_time` - Current time in seconds since Unix epoch.
+///- `step` - Time step in seconds, usually 30.
+///- `t0` - Time to start counting steps from, usually 0.
+///- `digits` - Number of digits in code, usually 6.
+///
+///## Panics
+///
+///- If `digits` is not within `1..=9`.
+///- If `step` is zero.
+///- If digest output is shorter than 20 bytes.
+pub fn totp<D: Digest>(secret: &[u8], unix_time: u64, step: u64, t0: u64, digits: u32) -> u32 {
+    hotp::<D>(secret, time_step(unix_time, step, t0), digits)
+}
+
+///Verifies TOTP `code`, accepting `window` time steps before and after `unix_time` to tolerate clock drift.
+///
+///Returns time step that matched `code`.
+///User should reject codes with time step not greater than last accepted one to prevent replay.
+///
+///## Panics
+///
+///- If `digits` is not within `1..=9`.
+///- If `step` is zero.
+///- If digest output is shorter than 20 bytes.
+pub fn verify_totp<D: Digest>(secret: &[u8], unix_time: u64, step: u64, t0: u64, digits: u32, window: u64, code: u32) -> Option<u64> {
+    let counter = time_step(unix_time, step, t0);
+    let start = counter.saturating_sub(window);
+    let window = (counter - start).saturating_add(window);
+
+    verify_hotp::<D>(secret, start, digits, window, code)
+}
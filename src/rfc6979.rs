@@ -0,0 +1,147 @@
+use crate::{Digest, HmacDrbg};
+
+///Size of largest supported group order (P-521).
+const MAX_ORDER_SIZE: usize = 66;
+
+///Shifts big endian number right by `shift` bits, where `shift` is less than 8.
+fn shift_right(num: &mut [u8], shift: u32) {
+    if shift == 0 {
+        return;
+    }
+
+    let mut carry = 0u8;
+    for byte in num.iter_mut() {
+        let next_carry = byte.wrapping_shl(8 - shift);
+        *byte = byte.wrapping_shr(shift) | carry;
+        carry = next_carry;
+    }
+}
+
+///Subtracts big endian number `src` from `dst` of equal size, assuming `dst` >= `src`.
+fn sub_be(dst: &mut [u8], src: &[u8]) {
+    let mut borrow = 0i16;
+
+    for (left, right) in dst.iter_mut().rev().zip(src.iter().rev()) {
+        let diff = *left as i16 - *right as i16 - borrow;
+        *left = diff as u8;
+        borrow = (diff < 0) as i16;
+    }
+}
+
+///Candidate nonce `k`, produced by `Rfc6979`.
+///
+///It is big endian integer in range `[1, q)`, with the same length as group order.
+#[derive(Clone, Copy)]
+pub struct Rfc6979Nonce {
+    bytes: [u8; MAX_ORDER_SIZE],
+    len: usize,
+}
+
+impl AsRef<[u8]> for Rfc6979Nonce {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+///Deterministic nonce generator as specified by RFC 6979.
+///
+///Infinite iterator of candidate nonces.
+///User should take next nonce whenever previous one is not suitable (e.g. produces `r = 0`).
+pub struct Rfc6979<D: Digest> {
+    drbg: HmacDrbg<D>,
+    order: [u8; MAX_ORDER_SIZE],
+    len: usize,
+    shift: u32,
+}
+
+impl<D: Digest> Rfc6979<D> {
+    ///Creates new generator.
+    ///
+    ///- `private_key` - Private key `x` as big endian bytes.
+    ///- `hash` - Message hash `H(m)`.
+    ///- `order` - Group order `q` as big endian bytes.
+    ///- `extra` - Additional data `k'` as described in section 3.6. Can be empty.
+    ///
+    ///## Panics
+    ///
+    ///- If `order` is zero or larger than 66 bytes.
+    ///- If `private_key` is larger than `order` bytes.
+    pub fn new(private_key: &[u8], hash: &[u8], order: &[u8], extra: &[u8]) -> Self {
+        let order = trim_zeros(order);
+        assert!(!order.is_empty(), "Group order cannot be zero");
+        assert!(order.len() <= MAX_ORDER_SIZE, "Group order is too large");
+
+        let len = order.len();
+        let shift = order[0].leading_zeros();
+        let mut order_bytes = [0u8; MAX_ORDER_SIZE];
+        order_bytes[..len].copy_from_slice(order);
+
+        let private_key = trim_zeros(private_key);
+        assert!(private_key.len() <= len, "Private key is larger than group order");
+        let mut key = [0u8; MAX_ORDER_SIZE];
+        key[len - private_key.len()..len].copy_from_slice(private_key);
+
+        let mut hash = bits2int(hash, len, shift);
+        if hash[..len] >= *order {
+            sub_be(&mut hash[..len], order);
+        }
+
+        Self {
+            drbg: HmacDrbg::new(&key[..len], &hash[..len], extra),
+            order: order_bytes,
+            len,
+            shift,
+        }
+    }
+}
+
+impl<D: Digest> Iterator for Rfc6979<D> {
+    type Item = Rfc6979Nonce;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut candidate = [0u8; MAX_ORDER_SIZE];
+
+        loop {
+            if self.drbg.generate(&[], &mut candidate[..self.len]).is_err() {
+                return None;
+            }
+
+            let bytes = bits2int(&candidate[..self.len], self.len, self.shift);
+            let nonce = &bytes[..self.len];
+            if nonce.iter().any(|byte| *byte != 0) && *nonce < self.order[..self.len] {
+                return Some(Rfc6979Nonce {
+                    bytes,
+                    len: self.len,
+                })
+            }
+        }
+    }
+}
+
+///Converts bit string to big endian integer of `len` bytes, leaving leftmost `qlen` bits.
+fn bits2int(input: &[u8], len: usize, shift: u32) -> [u8; MAX_ORDER_SIZE] {
+    let mut result = [0u8; MAX_ORDER_SIZE];
+
+    if input.len() >= len {
+        result[..len].copy_from_slice(&input[..len]);
+        shift_right(&mut result[..len], shift);
+    } else {
+        result[len - input.len()..len].copy_from_slice(input);
+    }
+
+    result
+}
+
+fn trim_zeros(num: &[u8]) -> &[u8] {
+    let start = num.iter().position(|byte| *byte != 0).unwrap_or(num.len());
+    &num[start..]
+}
+
+#[inline]
+///Creates RFC 6979 nonce generator for `private_key` and message `hash` over group of `order`.
+///
+///Refer to `Rfc6979::new` for details.
+pub fn rfc6979<D: Digest>(private_key: &[u8], hash: &[u8], order: &[u8]) -> Rfc6979<D> {
+    Rfc6979::new(private_key, hash, order, &[])
+}
@@ -0,0 +1,515 @@
+//!SCRAM authentication mechanism, as specified by RFC 5802 and RFC 7677.
+//!
+//!Use `Sha1` for SCRAM-SHA-1 and `Sha256` for SCRAM-SHA-256.
+//!
+//!Channel binding is not supported, so client always sends `n` GS2 flag, while server accepts `n` and `y`.
+//!Passwords are expected to be already prepared with SASLprep, which is no-op for ASCII passwords.
+//!
+//!Messages are written into user provided buffers.
+
+use crate::{base64, ct_eq, kdf, Digest, HmacKey};
+
+use core::fmt;
+
+///Maximum size of decoded salt.
+pub const MAX_SALT_SIZE: usize = 128;
+///Maximum size of `client-first-message-bare` kept by client.
+const MAX_BARE_SIZE: usize = 512;
+const GS2_HEADER: &str = "n,,";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///SCRAM error
+pub enum ScramError {
+    ///Message is malformed.
+    InvalidMessage,
+    ///Nonce contains invalid characters or server nonce doesn't extend client nonce.
+    InvalidNonce,
+    ///Message requires unsupported feature, like channel binding or mandatory extension.
+    Unsupported,
+    ///Output buffer is too small to fit message.
+    BufferTooSmall,
+    ///Server responded with `e=` error.
+    ServerError,
+    ///Client proof or server signature is not valid.
+    AuthenticationFailed,
+}
+
+impl fmt::Display for ScramError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScramError::InvalidMessage => fmt.write_str("SCRAM message is malformed"),
+            ScramError::InvalidNonce => fmt.write_str("SCRAM nonce is invalid"),
+            ScramError::Unsupported => fmt.write_str("SCRAM message requires unsupported feature"),
+            ScramError::BufferTooSmall => fmt.write_str("SCRAM output buffer is too small"),
+            ScramError::ServerError => fmt.write_str("SCRAM server responded with error"),
+            ScramError::AuthenticationFailed => fmt.write_str("SCRAM authentication failed"),
+        }
+    }
+}
+
+struct Writer<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> Writer<'a> {
+    #[inline]
+    fn new(buf: &'a mut [u8]) -> Self {
+        Self {
+            buf,
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, text: &str) -> Result<(), ScramError> {
+        let end = self.len + text.len();
+        match self.buf.get_mut(self.len..end) {
+            Some(dst) => dst.copy_from_slice(text.as_bytes()),
+            None => return Err(ScramError::BufferTooSmall),
+        }
+        self.len = end;
+        Ok(())
+    }
+
+    fn push_base64(&mut self, data: &[u8]) -> Result<(), ScramError> {
+        match base64::encode(data, base64::STANDARD, true, &mut self.buf[self.len..]) {
+            Some(size) => self.len += size,
+            None => return Err(ScramError::BufferTooSmall),
+        }
+        Ok(())
+    }
+
+    ///Writes `saslname`, escaping `,` and `=`.
+    fn push_name(&mut self, name: &str) -> Result<(), ScramError> {
+        for part in name.split_inclusive(&[',', '='][..]) {
+            match part.as_bytes()[part.len() - 1] {
+                b',' => {
+                    self.push(&part[..part.len() - 1])?;
+                    self.push("=2C")?;
+                },
+                b'=' => {
+                    self.push(&part[..part.len() - 1])?;
+                    self.push("=3D")?;
+                },
+                _ => self.push(part)?,
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn as_str(&self) -> &str {
+        //Only `str` and base64 are written
+        unsafe {
+            core::str::from_utf8_unchecked(&self.buf[..self.len])
+        }
+    }
+
+    #[inline]
+    fn into_str(self) -> &'a str {
+        unsafe {
+            core::str::from_utf8_unchecked(&self.buf[..self.len])
+        }
+    }
+}
+
+///Nonce must consist of printable ASCII characters except `,`.
+fn is_valid_nonce(nonce: &str) -> bool {
+    !nonce.is_empty() && nonce.bytes().all(|byte| byte > 0x20 && byte < 0x7f && byte != b',')
+}
+
+///Extracts value of attribute `name` from `part`.
+fn attribute(part: Option<&str>, name: char) -> Result<&str, ScramError> {
+    let part = match part {
+        Some(part) => part,
+        None => return Err(ScramError::InvalidMessage),
+    };
+
+    let mut chars = part.chars();
+    match (chars.next(), chars.next()) {
+        (Some(chr), Some('=')) if chr == name => Ok(chars.as_str()),
+        (Some('m'), Some('=')) => Err(ScramError::Unsupported),
+        _ => Err(ScramError::InvalidMessage),
+    }
+}
+
+///Computes `HMAC(key, AuthMessage)`.
+fn auth_signature<D: Digest>(key: &[u8], bare: &str, server_first: &str, client_final: &str) -> D::OutputType {
+    HmacKey::<D>::new(key).sign_parts(&[bare.as_bytes(), b",", server_first.as_bytes(), b",", client_final.as_bytes()])
+}
+
+///Server side credentials, derived from password.
+///
+///Server should store these along with salt and iteration count instead of password.
+pub struct Credentials<D: Digest> {
+    ///`StoredKey`
+    pub stored_key: D::OutputType,
+    ///`ServerKey`
+    pub server_key: D::OutputType,
+}
+
+impl<D: Digest> Clone for Credentials<D> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D: Digest> Copy for Credentials<D> {}
+
+impl<D: Digest> Credentials<D> {
+    ///Derives credentials from `password`.
+    ///
+    ///## Panics
+    ///
+    ///- If `iterations` is zero.
+    pub fn new(password: &[u8], salt: &[u8], iterations: u32) -> Self {
+        assert!(iterations > 0, "Iteration count must be positive");
+        let mut salted: D::OutputType = unsafe {
+            core::mem::MaybeUninit::zeroed().assume_init()
+        };
+        kdf::pbkdf2::<D>(password, salt, iterations, salted.as_mut());
+
+        let key = HmacKey::<D>::new(salted.as_ref());
+        let client_key = key.sign(b"Client Key");
+
+        let mut algo = D::new();
+        algo.update(client_key.as_ref());
+
+        Self {
+            stored_key: algo.result(),
+            server_key: key.sign(b"Server Key"),
+        }
+    }
+}
+
+///Client state before receiving `server-first-message`.
+pub struct Client<'a, D: Digest> {
+    password: &'a [u8],
+    bare: [u8; MAX_BARE_SIZE],
+    bare_len: usize,
+    nonce_len: usize,
+    _digest: core::marker::PhantomData<D>,
+}
+
+impl<'a, D: Digest> Client<'a, D> {
+    ///Creates new client.
+    ///
+    ///- `username` - User name, which is escaped as necessary.
+    ///- `password` - Password, prepared with SASLprep.
+    ///- `nonce` - Random printable nonce, excluding `,`.
+    pub fn new(username: &str, password: &'a [u8], nonce: &str) -> Result<Self, ScramError> {
+        if !is_valid_nonce(nonce) {
+            return Err(ScramError::InvalidNonce);
+        }
+
+        let mut bare = [0u8; MAX_BARE_SIZE];
+        let mut writer = Writer::new(&mut bare);
+        writer.push("n=")?;
+        writer.push_name(username)?;
+        writer.push(",r=")?;
+        writer.push(nonce)?;
+        let bare_len = writer.len;
+
+        Ok(Self {
+            password,
+            bare,
+            bare_len,
+            nonce_len: nonce.len(),
+            _digest: core::marker::PhantomData,
+        })
+    }
+
+    #[inline]
+    fn bare(&self) -> &str {
+        unsafe {
+            core::str::from_utf8_unchecked(&self.bare[..self.bare_len])
+        }
+    }
+
+    #[inline]
+    fn nonce(&self) -> &str {
+        &self.bare()[self.bare_len - self.nonce_len..]
+    }
+
+    ///Writes `client-first-message` into `out`.
+    pub fn client_first<'b>(&self, out: &'b mut [u8]) -> Result<&'b str, ScramError> {
+        let mut writer = Writer::new(out);
+        writer.push(GS2_HEADER)?;
+        writer.push(self.bare())?;
+        Ok(writer.into_str())
+    }
+
+    ///Handles `server-first-message`, writing `client-final-message` into `out`.
+    ///
+    ///Returns state to verify `server-final-message` with.
+    pub fn client_final<'b>(&self, server_first: &str, out: &'b mut [u8]) -> Result<(ClientFinal<D>, &'b str), ScramError> {
+        let mut parts = server_first.split(',');
+        let nonce = attribute(parts.next(), 'r')?;
+        let salt = attribute(parts.next(), 's')?;
+        let iterations = attribute(parts.next(), 'i')?;
+
+        if !is_valid_nonce(nonce) || nonce.len() <= self.nonce_len || !nonce.starts_with(self.nonce()) {
+            return Err(ScramError::InvalidNonce);
+        }
+
+        let mut salt_buf = [0u8; MAX_SALT_SIZE];
+        let salt = match base64::decode(salt.as_bytes(), base64::STANDARD, &mut salt_buf) {
+            Some(size) => &salt_buf[..size],
+            None => return Err(ScramError::InvalidMessage),
+        };
+        let iterations = match iterations.bytes().all(|byte| byte.is_ascii_digit()) {
+            true => iterations.parse::<u32>().unwrap_or(0),
+            false => 0,
+        };
+        if iterations == 0 {
+            return Err(ScramError::InvalidMessage);
+        }
+
+        let mut writer = Writer::new(out);
+        writer.push("c=")?;
+        writer.push_base64(GS2_HEADER.as_bytes())?;
+        writer.push(",r=")?;
+        writer.push(nonce)?;
+
+        let mut salted: D::OutputType = unsafe {
+            core::mem::MaybeUninit::zeroed().assume_init()
+        };
+        kdf::pbkdf2::<D>(self.password, salt, iterations, salted.as_mut());
+        let key = HmacKey::<D>::new(salted.as_ref());
+
+        let mut client_key = key.sign(b"Client Key");
+        let mut algo = D::new();
+        algo.update(client_key.as_ref());
+        let stored_key = algo.result();
+
+        let client_signature = auth_signature::<D>(stored_key.as_ref(), self.bare(), server_first, writer.as_str());
+        let server_signature = auth_signature::<D>(key.sign(b"Server Key").as_ref(), self.bare(), server_first, writer.as_str());
+
+        for (byte, signature) in client_key.as_mut().iter_mut().zip(client_signature.as_ref()) {
+            *byte ^= signature;
+        }
+        writer.push(",p=")?;
+        writer.push_base64(client_key.as_ref())?;
+
+        Ok((ClientFinal { server_signature }, writer.into_str()))
+    }
+}
+
+///Client state before receiving `server-final-message`.
+pub struct ClientFinal<D: Digest> {
+    server_signature: D::OutputType,
+}
+
+impl<D: Digest> ClientFinal<D> {
+    ///Verifies server signature within `server-final-message`.
+    pub fn verify(&self, server_final: &str) -> Result<(), ScramError> {
+        let mut parts = server_final.split(',');
+        let signature = match attribute(parts.next(), 'v') {
+            Ok(signature) => signature,
+            Err(_) if server_final.starts_with("e=") => return Err(ScramError::ServerError),
+            Err(error) => return Err(error),
+        };
+
+        let mut actual: D::OutputType = unsafe {
+            core::mem::MaybeUninit::zeroed().assume_init()
+        };
+        match base64::decode(signature.as_bytes(), base64::STANDARD, actual.as_mut()) {
+            Some(size) if size == actual.as_ref().len() => (),
+            _ => return Err(ScramError::InvalidMessage),
+        }
+
+        match ct_eq(actual.as_ref(), self.server_signature.as_ref()) {
+            true => Ok(()),
+            false => Err(ScramError::AuthenticationFailed),
+        }
+    }
+}
+
+///Server state after receiving `client-first-message`.
+pub struct Server<'a, D: Digest> {
+    gs2_header: &'a str,
+    authzid: Option<&'a str>,
+    bare: &'a str,
+    username: &'a str,
+    nonce: &'a str,
+    _digest: core::marker::PhantomData<D>,
+}
+
+impl<'a, D: Digest> Server<'a, D> {
+    ///Parses `client-first-message`.
+    pub fn new(client_first: &'a str) -> Result<Self, ScramError> {
+        let mut parts = client_first.splitn(3, ',');
+        match parts.next() {
+            Some("n") | Some("y") => (),
+            Some(flag) if flag.starts_with("p=") => return Err(ScramError::Unsupported),
+            _ => return Err(ScramError::InvalidMessage),
+        }
+        let authzid = match parts.next() {
+            Some("") => None,
+            Some(authzid) => Some(attribute(Some(authzid), 'a')?),
+            None => return Err(ScramError::InvalidMessage),
+        };
+        let bare = match parts.next() {
+            Some(bare) => bare,
+            None => return Err(ScramError::InvalidMessage),
+        };
+
+        let mut parts = bare.split(',');
+        let username = attribute(parts.next(), 'n')?;
+        let nonce = attribute(parts.next(), 'r')?;
+        if !is_valid_nonce(nonce) {
+            return Err(ScramError::InvalidNonce);
+        }
+
+        Ok(Self {
+            gs2_header: &client_first[..client_first.len() - bare.len()],
+            authzid,
+            bare,
+            username,
+            nonce,
+            _digest: core::marker::PhantomData,
+        })
+    }
+
+    #[inline]
+    ///Returns user name in its escaped `saslname` form.
+    ///
+    ///Use `decode_username` to unescape it.
+    pub fn username(&self) -> &'a str {
+        self.username
+    }
+
+    ///Writes unescaped user name into `out`.
+    pub fn decode_username<'b>(&self, out: &'b mut [u8]) -> Result<&'b str, ScramError> {
+        let mut writer = Writer::new(out);
+        let mut parts = self.username.split('=');
+        if let Some(part) = parts.next() {
+            writer.push(part)?;
+        }
+        for part in parts {
+            match part.get(..2) {
+                Some("2C") => writer.push(",")?,
+                Some("3D") => writer.push("=")?,
+                _ => return Err(ScramError::InvalidMessage),
+            }
+            writer.push(&part[2..])?;
+        }
+        Ok(writer.into_str())
+    }
+
+    #[inline]
+    ///Returns authorization identity, if any.
+    pub fn authzid(&self) -> Option<&'a str> {
+        self.authzid
+    }
+
+    ///Writes `server-first-message` into `out`.
+    ///
+    ///- `nonce` - Random printable server nonce, excluding `,`.
+    ///- `salt` - User's salt.
+    ///- `iterations` - User's iteration count.
+    ///- `credentials` - User's credentials, derived with the same `salt` and `iterations`.
+    ///
+    ///## Panics
+    ///
+    ///- If `iterations` is zero, as client must reject such message.
+    pub fn server_first<'b>(&self, nonce: &str, salt: &[u8], iterations: u32, credentials: &Credentials<D>, out: &'b mut [u8]) -> Result<ServerFinal<'a, 'b, D>, ScramError> {
+        assert!(iterations > 0, "Iteration count must be positive");
+        if !is_valid_nonce(nonce) {
+            return Err(ScramError::InvalidNonce);
+        }
+
+        let mut iterations_buf = [0u8; 10];
+        let mut cursor = iterations_buf.len();
+        let mut num = iterations;
+        loop {
+            cursor -= 1;
+            iterations_buf[cursor] = b'0' + (num % 10) as u8;
+            num /= 10;
+            if num == 0 {
+                break;
+            }
+        }
+
+        let mut writer = Writer::new(out);
+        writer.push("r=")?;
+        writer.push(self.nonce)?;
+        writer.push(nonce)?;
+        writer.push(",s=")?;
+        writer.push_base64(salt)?;
+        writer.push(",i=")?;
+        writer.push(unsafe { core::str::from_utf8_unchecked(&iterations_buf[cursor..]) })?;
+
+        Ok(ServerFinal {
+            gs2_header: self.gs2_header,
+            bare: self.bare,
+            server_first: writer.into_str(),
+            nonce_len: self.nonce.len() + nonce.len(),
+            credentials: *credentials,
+        })
+    }
+}
+
+///Server state before receiving `client-final-message`.
+pub struct ServerFinal<'a, 'b, D: Digest> {
+    gs2_header: &'a str,
+    bare: &'a str,
+    server_first: &'b str,
+    nonce_len: usize,
+    credentials: Credentials<D>,
+}
+
+impl<'a, 'b, D: Digest> ServerFinal<'a, 'b, D> {
+    #[inline]
+    ///Returns `server-first-message` to send.
+    pub fn server_first(&self) -> &'b str {
+        self.server_first
+    }
+
+    ///Verifies client proof within `client-final-message`, writing `server-final-message` into `out`.
+    pub fn server_final<'c>(&self, client_final: &str, out: &'c mut [u8]) -> Result<&'c str, ScramError> {
+        let (without_proof, proof) = match client_final.rfind(",p=") {
+            Some(idx) => (&client_final[..idx], &client_final[idx + 3..]),
+            None => return Err(ScramError::InvalidMessage),
+        };
+
+        let mut parts = without_proof.split(',');
+        let channel_binding = attribute(parts.next(), 'c')?;
+        let nonce = attribute(parts.next(), 'r')?;
+
+        let mut gs2_header = [0u8; MAX_BARE_SIZE];
+        match base64::decode(channel_binding.as_bytes(), base64::STANDARD, &mut gs2_header) {
+            Some(size) if gs2_header[..size] == *self.gs2_header.as_bytes() => (),
+            _ => return Err(ScramError::AuthenticationFailed),
+        }
+        if nonce != &self.server_first[2..2 + self.nonce_len] {
+            return Err(ScramError::InvalidNonce);
+        }
+
+        let mut client_key: D::OutputType = unsafe {
+            core::mem::MaybeUninit::zeroed().assume_init()
+        };
+        match base64::decode(proof.as_bytes(), base64::STANDARD, client_key.as_mut()) {
+            Some(size) if size == client_key.as_ref().len() => (),
+            _ => return Err(ScramError::InvalidMessage),
+        }
+
+        let client_signature = auth_signature::<D>(self.credentials.stored_key.as_ref(), self.bare, self.server_first, without_proof);
+        for (byte, signature) in client_key.as_mut().iter_mut().zip(client_signature.as_ref()) {
+            *byte ^= signature;
+        }
+
+        let mut algo = D::new();
+        algo.update(client_key.as_ref());
+        if !ct_eq(algo.result().as_ref(), self.credentials.stored_key.as_ref()) {
+            return Err(ScramError::AuthenticationFailed);
+        }
+
+        let server_signature = auth_signature::<D>(self.credentials.server_key.as_ref(), self.bare, self.server_first, without_proof);
+        let mut writer = Writer::new(out);
+        writer.push("v=")?;
+        writer.push_base64(server_signature.as_ref())?;
+        Ok(writer.into_str())
+    }
+}
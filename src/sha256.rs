@@ -1,8 +1,16 @@
+use crate::state::{self, StateError};
+
+use core::fmt;
+
 const BLOCK_SIZE: usize = 64;
 const RESULT_SIZE: usize = 32;
 const STATE_SIZE: usize = 8;
+///Maximum length of input in bytes, as message must be shorter than 2^64 bits.
+const MAX_LEN: u64 = (1 << 61) - 1;
+///Size of exported state.
+const EXPORT_SIZE: usize = state::HEADER_SIZE + 8 + STATE_SIZE * 4 + BLOCK_SIZE;
 const INIT_STATE: [u32; STATE_SIZE] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
-const K256: [u32; 64] = [
+pub(crate) const K256: [u32; 64] = [
 	0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
 	0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
 	0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
@@ -181,6 +189,21 @@ const fn sha256_transform(state: [u32; STATE_SIZE], cursor: usize, input: &[u8])
     ]
 }
 
+///Compresses whole blocks of `input`, using SHA extensions when available.
+fn compress(state: &mut [u32; STATE_SIZE], input: &[u8]) {
+    #[cfg(all(feature = "sha-ni", target_arch = "x86_64"))]
+    if crate::cpu::has_sha_ni() {
+        //Safety: CPU support is checked above.
+        return unsafe { crate::sha_ni::sha256_compress(state, input) };
+    }
+
+    let mut cursor = 0;
+    while cursor < input.len() {
+        *state = sha256_transform(*state, cursor, input);
+        cursor += BLOCK_SIZE;
+    }
+}
+
 ///const `SHA256` algorithm implementation
 pub const fn sha256(input: &[u8]) -> [u8; RESULT_SIZE] {
     let mut state = INIT_STATE;
@@ -244,11 +267,76 @@ pub const fn sha256(input: &[u8]) -> [u8; RESULT_SIZE] {
     ]
 }
 
+///const `HMAC-SHA256` algorithm implementation
+///
+///- `input` - Data to hash.
+///- `secret` - Data to derive HMAC's key.
+pub const fn hmac_sha256(input: &[u8], secret: &[u8]) -> [u8; RESULT_SIZE] {
+    let (inner_key, outer_key) = match secret.len() <= BLOCK_SIZE {
+        true => crate::hmac_pads::<BLOCK_SIZE>(secret),
+        false => crate::hmac_pads::<BLOCK_SIZE>(&sha256(secret)),
+    };
+
+    let inner_result = Sha256::new().const_update(&inner_key).const_update(input).const_result();
+    Sha256::new().const_update(&outer_key).const_update(&inner_result).const_result()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Raw `SHA256` state for low-level compression.
+///
+///No padding or length is applied, allowing custom initial values and padding schemes.
+pub struct Sha256State(pub [u32; STATE_SIZE]);
+
+impl Sha256State {
+    ///Creates state with standard initial value.
+    pub const fn new() -> Self {
+        Self(INIT_STATE)
+    }
+
+    ///Compresses single `block`.
+    pub const fn compress(self, block: &[u8; BLOCK_SIZE]) -> Self {
+        Self(sha256_transform(self.0, 0, block))
+    }
+
+    ///Compresses `blocks` in order.
+    pub fn compress_blocks(&mut self, blocks: &[[u8; BLOCK_SIZE]]) {
+        for block in blocks {
+            compress(&mut self.0, block);
+        }
+    }
+
+    ///Serializes state words in big endian, as in digest output.
+    pub const fn to_bytes(&self) -> [u8; STATE_SIZE * 4] {
+        let mut out = [0u8; STATE_SIZE * 4];
+        let mut idx = 0;
+        while idx < STATE_SIZE {
+            let word = self.0[idx].to_be_bytes();
+            let mut byte = 0;
+            while byte < 4 {
+                out[idx * 4 + byte] = word[byte];
+                byte += 1;
+            }
+            idx += 1;
+        }
+        out
+    }
+}
+
+impl Default for Sha256State {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 ///`Sha256` algorithm implementation
+#[derive(Clone, Copy)]
 pub struct Sha256 {
     state: [u32; STATE_SIZE],
     len: u64,
     buffer: [u8; BLOCK_SIZE],
+    ///Number of bits in final partial byte, set by `update_bits`.
+    bits: u8,
 }
 
 
@@ -258,7 +346,8 @@ impl Sha256 {
         Self {
             state: INIT_STATE,
             len: 0,
-            buffer: [0; BLOCK_SIZE]
+            buffer: [0; BLOCK_SIZE],
+            bits: 0,
         }
     }
 
@@ -268,8 +357,15 @@ impl Sha256 {
     }
 
     ///Hashes input
+    ///
+    ///## Panics
+    ///
+    ///- If total length of input reaches 2^64 bits.
+    ///- If called after `update_bits` with partial byte.
     pub const fn const_update(mut self, input: &[u8]) -> Self {
         let num = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
+        assert!(self.bits == 0, "Input after partial byte");
+        assert!(input.len() as u64 <= MAX_LEN - self.len, "Input length exceeds SHA-256 limit of 2^64 bits");
         self.len += input.len() as u64;
 
         let mut cursor = 0;
@@ -311,8 +407,15 @@ impl Sha256 {
     }
 
     ///Hashes input
+    ///
+    ///## Panics
+    ///
+    ///- If total length of input reaches 2^64 bits.
+    ///- If called after `update_bits` with partial byte.
     pub fn update(&mut self, input: &[u8]) {
         let mut num = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
+        assert!(self.bits == 0, "Input after partial byte");
+        assert!(input.len() as u64 <= MAX_LEN - self.len, "Input length exceeds SHA-256 limit of 2^64 bits");
         self.len += input.len() as u64;
 
         let mut cursor = 0;
@@ -327,14 +430,13 @@ impl Sha256 {
             }
 
             buffer.copy_from_slice(&input[..num]);
-            self.state = sha256_transform(self.state, 0, &self.buffer);
+            compress(&mut self.state, &self.buffer);
             cursor += num
         }
 
-        while input.len() - cursor >= BLOCK_SIZE {
-            self.state = sha256_transform(self.state, cursor, input);
-            cursor += BLOCK_SIZE;
-        }
+        let end = cursor + (input.len() - cursor) / BLOCK_SIZE * BLOCK_SIZE;
+        compress(&mut self.state, &input[cursor..end]);
+        cursor = end;
 
         let remains = input.len() - cursor;
         if remains > 0 {
@@ -342,11 +444,64 @@ impl Sha256 {
         }
     }
 
+    ///Hashes whole `blocks` directly, bypassing internal buffering.
+    ///
+    ///If previous input left incomplete block in buffer, `blocks` are buffered as by `update`.
+    ///
+    ///## Panics
+    ///
+    ///- If called after partial byte was already hashed.
+    ///- If total length of input reaches 2^64 bits.
+    pub fn compress_blocks(&mut self, blocks: &[[u8; BLOCK_SIZE]]) {
+        assert!(self.bits == 0, "Input after partial byte");
+        if self.len & (BLOCK_SIZE as u64 - 1) != 0 {
+            for block in blocks {
+                self.update(block);
+            }
+            return;
+        }
+
+        let len = blocks.len() as u64 * BLOCK_SIZE as u64;
+        assert!(len <= MAX_LEN - self.len, "Input length exceeds SHA-256 limit of 2^64 bits");
+
+        for block in blocks {
+            compress(&mut self.state, block);
+        }
+        self.len += len;
+    }
+
+    ///Hashes first `bit_len` bits of `input`, as bit-oriented message of FIPS 180-4.
+    ///
+    ///Bits are taken from most significant bit of each byte.
+    ///If `bit_len` is not multiple of 8, it must be final input before finalization.
+    ///
+    ///## Panics
+    ///
+    ///- If `bit_len` exceeds number of bits in `input`.
+    ///- If called after partial byte was already hashed.
+    ///- If total length of input reaches 2^64 bits.
+    pub fn update_bits(&mut self, input: &[u8], bit_len: usize) {
+        assert!(bit_len <= input.len() * 8, "Bit length exceeds input");
+
+        let bytes = bit_len / 8;
+        self.update(&input[..bytes]);
+
+        let bits = bit_len % 8;
+        if bits > 0 {
+            let pos = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
+            self.buffer[pos] = input[bytes] & (0xFF << (8 - bits));
+            self.bits = bits as u8;
+        }
+    }
+
     ///Finalizes algorithm, returning the hash.
     pub const fn const_result(mut self) -> [u8; RESULT_SIZE] {
         let mut pos = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
 
-        self.buffer[pos] = 0x80;
+        self.buffer[pos] = match self.bits {
+            0 => 0x80,
+            bits => self.buffer[pos] | (0x80 >> bits),
+        };
         pos += 1;
 
         while pos != (BLOCK_SIZE - core::mem::size_of::<u64>()) {
@@ -360,7 +515,7 @@ impl Sha256 {
             pos += 1;
         }
 
-        let len = self.len.wrapping_shl(3).to_be_bytes();
+        let len = (self.len.wrapping_shl(3) | self.bits as u64).to_be_bytes();
         self.buffer[pos] = len[0];
         self.buffer[pos + 1] = len[1];
         self.buffer[pos + 2] = len[2];
@@ -392,54 +547,75 @@ impl Sha256 {
         ]
     }
 
-    ///Finalizes algorithm, returning the hash.
-    pub fn result(&mut self) -> [u8; RESULT_SIZE] {
-        let mut pos = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
-
-        self.buffer[pos] = 0x80;
-        pos += 1;
+    ///Returns hash of all input so far, leaving state unchanged.
+    ///
+    ///More input can be added afterwards, extending already hashed data.
+    pub fn result(&self) -> [u8; RESULT_SIZE] {
+        (*self).const_result()
+    }
 
-        while pos != (BLOCK_SIZE - core::mem::size_of::<u64>()) {
-            pos &= BLOCK_SIZE - 1;
+    ///Returns hash of all input so far and resets algorithm's state.
+    pub fn finalize_reset(&mut self) -> [u8; RESULT_SIZE] {
+        let result = self.result();
+        self.reset();
+        result
+    }
 
-            if pos == 0 {
-                self.state = sha256_transform(self.state, 0, &self.buffer);
-            }
+    ///Writes hash of all input so far into `out`, leaving state unchanged.
+    ///
+    ///## Panics
+    ///
+    ///If `out` length differs from output size.
+    pub fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.result());
+    }
 
-            self.buffer[pos] = 0;
-            pos += 1;
+    ///Exports current state, allowing to resume hashing later via `import_state`.
+    ///
+    ///Buffered input is included, but no other input can be recovered from it.
+    ///
+    ///## Panics
+    ///
+    ///If partial byte was hashed by `update_bits`.
+    pub fn export_state(&self) -> [u8; EXPORT_SIZE] {
+        assert!(self.bits == 0, "Cannot export state with partial byte");
+        let mut out = [0u8; EXPORT_SIZE];
+        state::write_header(&mut out, state::SHA256);
+
+        let (len, rest) = out[state::HEADER_SIZE..].split_at_mut(8);
+        len.copy_from_slice(&self.len.to_be_bytes());
+
+        let (words, buffer) = rest.split_at_mut(STATE_SIZE * 4);
+        for (chunk, word) in words.chunks_mut(4).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
         }
 
-        let len = self.len.wrapping_shl(3).to_be_bytes();
-        self.buffer[pos] = len[0];
-        self.buffer[pos + 1] = len[1];
-        self.buffer[pos + 2] = len[2];
-        self.buffer[pos + 3] = len[3];
-        self.buffer[pos + 4] = len[4];
-        self.buffer[pos + 5] = len[5];
-        self.buffer[pos + 6] = len[6];
-        self.buffer[pos + 7] = len[7];
+        let tail = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
+        buffer[..tail].copy_from_slice(&self.buffer[..tail]);
+        out
+    }
 
-        self.state = sha256_transform(self.state, 0, &self.buffer);
+    ///Restores state, previously exported by `export_state`.
+    pub fn import_state(input: &[u8]) -> Result<Self, StateError> {
+        state::check_header(input, EXPORT_SIZE, state::SHA256)?;
+
+        let (len, rest) = input[state::HEADER_SIZE..].split_at(8);
+        let mut result = Self::new();
+        let mut len_bytes = [0u8; 8];
+        len_bytes.copy_from_slice(len);
+        result.len = u64::from_be_bytes(len_bytes);
+        if result.len > MAX_LEN {
+            return Err(StateError::InvalidLength);
+        }
 
-        let a = self.state[0].to_be_bytes();
-        let b = self.state[1].to_be_bytes();
-        let c = self.state[2].to_be_bytes();
-        let d = self.state[3].to_be_bytes();
-        let e = self.state[4].to_be_bytes();
-        let f = self.state[5].to_be_bytes();
-        let g = self.state[6].to_be_bytes();
-        let h = self.state[7].to_be_bytes();
-        [
-            a[0], a[1], a[2], a[3],
-            b[0], b[1], b[2], b[3],
-            c[0], c[1], c[2], c[3],
-            d[0], d[1], d[2], d[3],
-            e[0], e[1], e[2], e[3],
-            f[0], f[1], f[2], f[3],
-            g[0], g[1], g[2], g[3],
-            h[0], h[1], h[2], h[3],
-        ]
+        let (words, buffer) = rest.split_at(STATE_SIZE * 4);
+        for (word, chunk) in result.state.iter_mut().zip(words.chunks(4)) {
+            *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+
+        let tail = (result.len & (BLOCK_SIZE as u64 - 1)) as usize;
+        result.buffer[..tail].copy_from_slice(&buffer[..tail]);
+        Ok(result)
     }
 }
 
@@ -447,6 +623,11 @@ impl super::Digest for Sha256 {
     type OutputType = [u8; RESULT_SIZE];
     type BlockType = [u8; BLOCK_SIZE];
 
+    const OUTPUT_SIZE: usize = RESULT_SIZE;
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+    const NAME: &'static str = "SHA-256";
+    const OID: Option<&'static [u8]> = Some(&[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01]);
+
     #[inline(always)]
     fn new() -> Self {
         Self::new()
@@ -463,7 +644,31 @@ impl super::Digest for Sha256 {
     }
 
     #[inline(always)]
-    fn result(&mut self) -> Self::OutputType {
+    fn result(&self) -> Self::OutputType {
         self.result()
     }
+
+    #[inline(always)]
+    fn finalize_reset(&mut self) -> Self::OutputType {
+        self.finalize_reset()
+    }
+
+    #[inline(always)]
+    fn finalize_into(&self, out: &mut [u8]) {
+        self.finalize_into(out);
+    }
+}
+
+impl Default for Sha256 {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Sha256 {
+    ///Writes only number of hashed bytes, without state or buffered input.
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Sha256").field("len", &self.len).finish_non_exhaustive()
+    }
 }
@@ -0,0 +1,175 @@
+use crate::sha512::{Sha512, EXPORT_SIZE};
+use crate::state::{self, StateError};
+
+use core::fmt;
+
+const BLOCK_SIZE: usize = 128;
+const RESULT_SIZE: usize = 48;
+const INIT_STATE: [u64; 8] = [0xcbbb9d5dc1059ed8, 0x629a292a367cd507, 0x9159015a3070dd17, 0x152fecd8f70e5939, 0x67332667ffc00b31, 0x8eb44a8768581511, 0xdb0c2e0d64f98fa7, 0x47b5481dbefa4fa4];
+
+const fn truncate(hash: [u8; 64]) -> [u8; RESULT_SIZE] {
+    let mut result = [0; RESULT_SIZE];
+    let mut idx = 0;
+    while idx < RESULT_SIZE {
+        result[idx] = hash[idx];
+        idx += 1;
+    }
+    result
+}
+
+#[inline]
+///const `SHA384` algorithm implementation
+pub const fn sha384(input: &[u8]) -> [u8; RESULT_SIZE] {
+    Sha384::new().const_update(input).const_result()
+}
+
+///`Sha384` algorithm implementation
+///
+///It is `Sha512` with different initial state and truncated output.
+#[derive(Clone, Copy)]
+pub struct Sha384 {
+    inner: Sha512,
+}
+
+impl Sha384 {
+    ///Creates new instance
+    pub const fn new() -> Self {
+        Self {
+            inner: Sha512::with_state(INIT_STATE),
+        }
+    }
+
+    ///Resets algorithm's state.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    ///Hashes input
+    ///
+    ///## Panics
+    ///
+    ///If total length of input reaches 2^128 bits.
+    pub const fn const_update(self, input: &[u8]) -> Self {
+        Self {
+            inner: self.inner.const_update(input),
+        }
+    }
+
+    #[inline(always)]
+    ///Hashes input
+    ///
+    ///## Panics
+    ///
+    ///If total length of input reaches 2^128 bits.
+    pub fn update(&mut self, input: &[u8]) {
+        self.inner.update(input);
+    }
+
+    #[inline(always)]
+    ///Hashes whole `blocks` directly, bypassing internal buffering.
+    ///
+    ///If previous input left incomplete block in buffer, `blocks` are buffered as by `update`.
+    ///
+    ///## Panics
+    ///
+    ///If total length of input reaches 2^128 bits.
+    pub fn compress_blocks(&mut self, blocks: &[[u8; BLOCK_SIZE]]) {
+        self.inner.compress_blocks(blocks);
+    }
+
+    ///Finalizes algorithm, returning the hash.
+    pub const fn const_result(self) -> [u8; RESULT_SIZE] {
+        truncate(self.inner.const_result())
+    }
+
+    ///Returns hash of all input so far, leaving state unchanged.
+    ///
+    ///More input can be added afterwards, extending already hashed data.
+    pub fn result(&self) -> [u8; RESULT_SIZE] {
+        truncate(self.inner.result())
+    }
+
+    ///Returns hash of all input so far and resets algorithm's state.
+    pub fn finalize_reset(&mut self) -> [u8; RESULT_SIZE] {
+        let result = self.result();
+        self.reset();
+        result
+    }
+
+    ///Writes hash of all input so far into `out`, leaving state unchanged.
+    ///
+    ///## Panics
+    ///
+    ///If `out` length differs from output size.
+    pub fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.result());
+    }
+
+    ///Exports current state, allowing to resume hashing later via `import_state`.
+    ///
+    ///Buffered input is included, but no other input can be recovered from it.
+    pub fn export_state(&self) -> [u8; EXPORT_SIZE] {
+        self.inner.export_tagged(state::SHA384)
+    }
+
+    ///Restores state, previously exported by `export_state`.
+    pub fn import_state(input: &[u8]) -> Result<Self, StateError> {
+        Ok(Self {
+            inner: Sha512::import_tagged(input, state::SHA384)?,
+        })
+    }
+}
+
+impl super::Digest for Sha384 {
+    type OutputType = [u8; RESULT_SIZE];
+    type BlockType = [u8; BLOCK_SIZE];
+
+    const OUTPUT_SIZE: usize = RESULT_SIZE;
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+    const NAME: &'static str = "SHA-384";
+    const OID: Option<&'static [u8]> = Some(&[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02]);
+
+    #[inline(always)]
+    fn new() -> Self {
+        Self::new()
+    }
+
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.reset();
+    }
+
+    #[inline(always)]
+    fn update(&mut self, input: &[u8]) {
+        self.update(input);
+    }
+
+    #[inline(always)]
+    fn result(&self) -> Self::OutputType {
+        self.result()
+    }
+
+    #[inline(always)]
+    fn finalize_reset(&mut self) -> Self::OutputType {
+        self.finalize_reset()
+    }
+
+    #[inline(always)]
+    fn finalize_into(&self, out: &mut [u8]) {
+        self.finalize_into(out);
+    }
+}
+
+impl Default for Sha384 {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Sha384 {
+    ///Writes only number of hashed bytes, without state or buffered input.
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Sha384").field("len", &self.inner.len()).finish_non_exhaustive()
+    }
+}
@@ -1,6 +1,14 @@
+use crate::state::{self, StateError};
+
+use core::fmt;
+
 const BLOCK_SIZE: usize = 128;
 const RESULT_SIZE: usize = 64;
 const STATE_SIZE: usize = 8;
+///Maximum length of input in bytes, as message must be shorter than 2^128 bits.
+const MAX_LEN: u128 = (1 << 125) - 1;
+///Size of exported state.
+pub(crate) const EXPORT_SIZE: usize = state::HEADER_SIZE + 16 + STATE_SIZE * 8 + BLOCK_SIZE;
 const INIT_STATE: [u64; STATE_SIZE] = [0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1, 0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179];
 const K512: [u64; 80] = [
 	0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc,
@@ -25,7 +33,46 @@ const K512: [u64; 80] = [
 	0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817
 ];
 
+#[inline(always)]
 const fn sha512_transform(state: [u64; STATE_SIZE], cursor: usize, input: &[u8]) -> [u64; STATE_SIZE] {
+    sha512_rounds(state, &sha512_schedule(cursor, input))
+}
+
+///Computes message schedule of block at `cursor`.
+pub(crate) const fn sha512_schedule(cursor: usize, input: &[u8]) -> [u64; 80] {
+    macro_rules! s0 {
+        ($in:expr) => {
+            ($in.rotate_right(7) ^ $in).rotate_right(1) ^ $in.wrapping_shr(7)
+        }
+    }
+
+    macro_rules! s1 {
+        ($in:expr) => {
+            ($in.rotate_right(42) ^ $in).rotate_right(19) ^ $in.wrapping_shr(6)
+        }
+    }
+
+    const fn read_u64(input: &[u8], cursor: usize) -> u64 {
+        u64::from_be_bytes([input[cursor], input[cursor + 1], input[cursor + 2], input[cursor + 3], input[cursor + 4], input[cursor + 5], input[cursor + 6], input[cursor + 7]])
+    }
+
+    let mut x = [0u64; 80];
+    let mut idx = 0;
+    while idx < 16 {
+        x[idx] = read_u64(input, cursor + idx * 8);
+        idx += 1;
+    }
+
+    while idx < x.len() {
+        x[idx] = s1!(x[idx - 2]).wrapping_add(x[idx - 7]).wrapping_add(s0!(x[idx - 15])).wrapping_add(x[idx - 16]);
+        idx += 1;
+    }
+
+    x
+}
+
+///Applies rounds over message schedule `x`.
+const fn sha512_rounds(state: [u64; STATE_SIZE], x: &[u64; 80]) -> [u64; STATE_SIZE] {
     let mut a = state[0];
     let mut b = state[1];
     let mut c = state[2];
@@ -47,18 +94,6 @@ const fn sha512_transform(state: [u64; STATE_SIZE], cursor: usize, input: &[u8])
         }
     }
 
-    macro_rules! s0 {
-        ($in:expr) => {
-            ($in.rotate_right(7) ^ $in).rotate_right(1) ^ $in.wrapping_shr(7)
-        }
-    }
-
-    macro_rules! s1 {
-        ($in:expr) => {
-            ($in.rotate_right(42) ^ $in).rotate_right(19) ^ $in.wrapping_shr(6)
-        }
-    }
-
     macro_rules! Ch {
         ($x:expr, $y:expr, $z:expr) => {
             (($z) ^ (($x) & (($y) ^ ($z))))
@@ -71,22 +106,6 @@ const fn sha512_transform(state: [u64; STATE_SIZE], cursor: usize, input: &[u8])
         }
     }
 
-    const fn read_u64(input: &[u8], cursor: usize) -> u64 {
-        u64::from_be_bytes([input[cursor], input[cursor + 1], input[cursor + 2], input[cursor + 3], input[cursor + 4], input[cursor + 5], input[cursor + 6], input[cursor + 7]])
-    }
-
-    let mut x = [0u64; 80];
-    let mut idx = 0;
-    while idx < 16 {
-        x[idx] = read_u64(input, cursor + idx * 8);
-        idx += 1;
-    }
-
-    while idx < x.len() {
-        x[idx] = s1!(x[idx - 2]).wrapping_add(x[idx - 7]).wrapping_add(s0!(x[idx - 15])).wrapping_add(x[idx - 16]);
-        idx += 1;
-    }
-
     macro_rules! R {
         ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr, $f:expr, $g:expr, $h:expr, $i:expr) => {
             $h = $h.wrapping_add(S1!($e).wrapping_add(Ch!($e, $f, $g)).wrapping_add(K512[$i]).wrapping_add(x[$i]));
@@ -95,7 +114,7 @@ const fn sha512_transform(state: [u64; STATE_SIZE], cursor: usize, input: &[u8])
         }
     }
 
-    idx = 0;
+    let mut idx = 0;
     while idx < 80 {
         R!(a, b, c, d, e, f, g, h, idx);
         R!(h, a, b, c, d, e, f, g, idx+1);
@@ -121,6 +140,32 @@ const fn sha512_transform(state: [u64; STATE_SIZE], cursor: usize, input: &[u8])
     ]
 }
 
+///Compresses whole blocks of `input`, computing message schedule via AVX2 or SSSE3 when available.
+fn compress(state: &mut [u64; STATE_SIZE], input: &[u8]) {
+    #[cfg(all(feature = "avx2", target_arch = "x86_64"))]
+    {
+        if crate::cpu::has_avx2() {
+            for block in input.chunks_exact(BLOCK_SIZE) {
+                //Safety: CPU support is checked above.
+                *state = sha512_rounds(*state, &unsafe { crate::sha512_simd::schedule_avx2(block) });
+            }
+            return;
+        } else if crate::cpu::has_ssse3() {
+            for block in input.chunks_exact(BLOCK_SIZE) {
+                //Safety: CPU support is checked above.
+                *state = sha512_rounds(*state, &unsafe { crate::sha512_simd::schedule_ssse3(block) });
+            }
+            return;
+        }
+    }
+
+    let mut cursor = 0;
+    while cursor < input.len() {
+        *state = sha512_transform(*state, cursor, input);
+        cursor += BLOCK_SIZE;
+    }
+}
+
 #[inline]
 ///const `SHA512` algorithm implementation
 pub const fn sha512(input: &[u8]) -> [u8; RESULT_SIZE] {
@@ -196,11 +241,76 @@ pub const fn sha512(input: &[u8]) -> [u8; RESULT_SIZE] {
     ]
 }
 
+///const `HMAC-SHA512` algorithm implementation
+///
+///- `input` - Data to hash.
+///- `secret` - Data to derive HMAC's key.
+pub const fn hmac_sha512(input: &[u8], secret: &[u8]) -> [u8; RESULT_SIZE] {
+    let (inner_key, outer_key) = match secret.len() <= BLOCK_SIZE {
+        true => crate::hmac_pads::<BLOCK_SIZE>(secret),
+        false => crate::hmac_pads::<BLOCK_SIZE>(&sha512(secret)),
+    };
+
+    let inner_result = Sha512::new().const_update(&inner_key).const_update(input).const_result();
+    Sha512::new().const_update(&outer_key).const_update(&inner_result).const_result()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Raw `SHA512` state for low-level compression.
+///
+///No padding or length is applied, allowing custom initial values and padding schemes.
+pub struct Sha512State(pub [u64; STATE_SIZE]);
+
+impl Sha512State {
+    ///Creates state with standard initial value.
+    pub const fn new() -> Self {
+        Self(INIT_STATE)
+    }
+
+    ///Compresses single `block`.
+    pub const fn compress(self, block: &[u8; BLOCK_SIZE]) -> Self {
+        Self(sha512_transform(self.0, 0, block))
+    }
+
+    ///Compresses `blocks` in order.
+    pub fn compress_blocks(&mut self, blocks: &[[u8; BLOCK_SIZE]]) {
+        for block in blocks {
+            compress(&mut self.0, block);
+        }
+    }
+
+    ///Serializes state words in big endian, as in digest output.
+    pub const fn to_bytes(&self) -> [u8; STATE_SIZE * 8] {
+        let mut out = [0u8; STATE_SIZE * 8];
+        let mut idx = 0;
+        while idx < STATE_SIZE {
+            let word = self.0[idx].to_be_bytes();
+            let mut byte = 0;
+            while byte < 8 {
+                out[idx * 8 + byte] = word[byte];
+                byte += 1;
+            }
+            idx += 1;
+        }
+        out
+    }
+}
+
+impl Default for Sha512State {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 ///`Sha512` algorithm implementation
+#[derive(Clone, Copy)]
 pub struct Sha512 {
     state: [u64; STATE_SIZE],
-    len: u64,
+    len: u128,
     buffer: [u8; BLOCK_SIZE],
+    ///Number of bits in final partial byte, set by `update_bits`.
+    bits: u8,
 }
 
 
@@ -210,19 +320,44 @@ impl Sha512 {
         Self {
             state: INIT_STATE,
             len: 0,
-            buffer: [0; BLOCK_SIZE]
+            buffer: [0; BLOCK_SIZE],
+            bits: 0,
+        }
+    }
+
+    #[cfg(any(feature = "sha384", feature = "sha512_256"))]
+    ///Creates new instance with custom initial state, used by truncated variants.
+    pub(crate) const fn with_state(state: [u64; STATE_SIZE]) -> Self {
+        Self {
+            state,
+            len: 0,
+            buffer: [0; BLOCK_SIZE],
+            bits: 0,
         }
     }
 
+    #[cfg(any(feature = "sha384", feature = "sha512_256"))]
+    ///Returns number of hashed bytes, used by truncated variants.
+    pub(crate) const fn len(&self) -> u128 {
+        self.len
+    }
+
     ///Resets algorithm's state.
     pub fn reset(&mut self) {
         *self = Self::new();
     }
 
     ///Hashes input
+    ///
+    ///## Panics
+    ///
+    ///- If total length of input reaches 2^128 bits.
+    ///- If called after `update_bits` with partial byte.
     pub const fn const_update(mut self, input: &[u8]) -> Self {
-        let num = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
-        self.len += input.len() as u64;
+        let num = (self.len & (BLOCK_SIZE as u128 - 1)) as usize;
+        assert!(self.bits == 0, "Input after partial byte");
+        assert!(input.len() as u128 <= MAX_LEN - self.len, "Input length exceeds SHA-512 limit of 2^128 bits");
+        self.len += input.len() as u128;
 
         let mut cursor = 0;
 
@@ -263,9 +398,16 @@ impl Sha512 {
     }
 
     ///Hashes input
+    ///
+    ///## Panics
+    ///
+    ///- If total length of input reaches 2^128 bits.
+    ///- If called after `update_bits` with partial byte.
     pub fn update(&mut self, input: &[u8]) {
-        let mut num = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
-        self.len += input.len() as u64;
+        let mut num = (self.len & (BLOCK_SIZE as u128 - 1)) as usize;
+        assert!(self.bits == 0, "Input after partial byte");
+        assert!(input.len() as u128 <= MAX_LEN - self.len, "Input length exceeds SHA-512 limit of 2^128 bits");
+        self.len += input.len() as u128;
 
         let mut cursor = 0;
 
@@ -279,14 +421,13 @@ impl Sha512 {
             }
 
             buffer.copy_from_slice(&input[..num]);
-            self.state = sha512_transform(self.state, 0, &self.buffer);
+            compress(&mut self.state, &self.buffer);
             cursor += num;
         }
 
-        while input.len() - cursor >= BLOCK_SIZE {
-            self.state = sha512_transform(self.state, cursor, input);
-            cursor += BLOCK_SIZE;
-        }
+        let end = cursor + (input.len() - cursor) / BLOCK_SIZE * BLOCK_SIZE;
+        compress(&mut self.state, &input[cursor..end]);
+        cursor = end;
 
         let remains = input.len() - cursor;
         if remains > 0 {
@@ -294,11 +435,64 @@ impl Sha512 {
         }
     }
 
+    ///Hashes whole `blocks` directly, bypassing internal buffering.
+    ///
+    ///If previous input left incomplete block in buffer, `blocks` are buffered as by `update`.
+    ///
+    ///## Panics
+    ///
+    ///- If called after partial byte was already hashed.
+    ///- If total length of input reaches 2^128 bits.
+    pub fn compress_blocks(&mut self, blocks: &[[u8; BLOCK_SIZE]]) {
+        assert!(self.bits == 0, "Input after partial byte");
+        if self.len & (BLOCK_SIZE as u128 - 1) != 0 {
+            for block in blocks {
+                self.update(block);
+            }
+            return;
+        }
+
+        let len = blocks.len() as u128 * BLOCK_SIZE as u128;
+        assert!(len <= MAX_LEN - self.len, "Input length exceeds SHA-512 limit of 2^128 bits");
+
+        for block in blocks {
+            compress(&mut self.state, block);
+        }
+        self.len += len;
+    }
+
+    ///Hashes first `bit_len` bits of `input`, as bit-oriented message of FIPS 180-4.
+    ///
+    ///Bits are taken from most significant bit of each byte.
+    ///If `bit_len` is not multiple of 8, it must be final input before finalization.
+    ///
+    ///## Panics
+    ///
+    ///- If `bit_len` exceeds number of bits in `input`.
+    ///- If called after partial byte was already hashed.
+    ///- If total length of input reaches 2^128 bits.
+    pub fn update_bits(&mut self, input: &[u8], bit_len: usize) {
+        assert!(bit_len <= input.len() * 8, "Bit length exceeds input");
+
+        let bytes = bit_len / 8;
+        self.update(&input[..bytes]);
+
+        let bits = bit_len % 8;
+        if bits > 0 {
+            let pos = (self.len & (BLOCK_SIZE as u128 - 1)) as usize;
+            self.buffer[pos] = input[bytes] & (0xFF << (8 - bits));
+            self.bits = bits as u8;
+        }
+    }
+
     ///Finalizes algorithm, returning the hash.
     pub const fn const_result(mut self) -> [u8; RESULT_SIZE] {
-        let mut pos = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
+        let mut pos = (self.len & (BLOCK_SIZE as u128 - 1)) as usize;
 
-        self.buffer[pos] = 0x80;
+        self.buffer[pos] = match self.bits {
+            0 => 0x80,
+            bits => self.buffer[pos] | (0x80 >> bits),
+        };
         pos += 1;
 
         while pos != (BLOCK_SIZE - (2 * core::mem::size_of::<u64>())) {
@@ -312,26 +506,23 @@ impl Sha512 {
             pos += 1;
         }
 
-        let len_lo = self.len.wrapping_shl(3).to_be_bytes();
-        let len_hi = self.len.wrapping_shr(64 - 3).to_be_bytes();
-
-        self.buffer[pos] = len_hi[0];
-        self.buffer[pos + 1] = len_hi[1];
-        self.buffer[pos + 2] = len_hi[2];
-        self.buffer[pos + 3] = len_hi[3];
-        self.buffer[pos + 4] = len_hi[4];
-        self.buffer[pos + 5] = len_hi[5];
-        self.buffer[pos + 6] = len_hi[6];
-        self.buffer[pos + 7] = len_hi[7];
-
-        self.buffer[pos + 8] = len_lo[0];
-        self.buffer[pos + 9] = len_lo[1];
-        self.buffer[pos + 10] = len_lo[2];
-        self.buffer[pos + 11] = len_lo[3];
-        self.buffer[pos + 12] = len_lo[4];
-        self.buffer[pos + 13] = len_lo[5];
-        self.buffer[pos + 14] = len_lo[6];
-        self.buffer[pos + 15] = len_lo[7];
+        let len = (self.len.wrapping_shl(3) | self.bits as u128).to_be_bytes();
+        self.buffer[pos] = len[0];
+        self.buffer[pos + 1] = len[1];
+        self.buffer[pos + 2] = len[2];
+        self.buffer[pos + 3] = len[3];
+        self.buffer[pos + 4] = len[4];
+        self.buffer[pos + 5] = len[5];
+        self.buffer[pos + 6] = len[6];
+        self.buffer[pos + 7] = len[7];
+        self.buffer[pos + 8] = len[8];
+        self.buffer[pos + 9] = len[9];
+        self.buffer[pos + 10] = len[10];
+        self.buffer[pos + 11] = len[11];
+        self.buffer[pos + 12] = len[12];
+        self.buffer[pos + 13] = len[13];
+        self.buffer[pos + 14] = len[14];
+        self.buffer[pos + 15] = len[15];
 
         self.state = sha512_transform(self.state, 0, &self.buffer);
 
@@ -355,65 +546,87 @@ impl Sha512 {
         ]
     }
 
-    ///Finalizes algorithm, returning the hash.
-    pub fn result(&mut self) -> [u8; RESULT_SIZE] {
-        let mut pos = (self.len & (BLOCK_SIZE as u64 - 1)) as usize;
+    ///Returns hash of all input so far, leaving state unchanged.
+    ///
+    ///More input can be added afterwards, extending already hashed data.
+    pub fn result(&self) -> [u8; RESULT_SIZE] {
+        (*self).const_result()
+    }
 
-        self.buffer[pos] = 0x80;
-        pos += 1;
+    ///Returns hash of all input so far and resets algorithm's state.
+    pub fn finalize_reset(&mut self) -> [u8; RESULT_SIZE] {
+        let result = self.result();
+        self.reset();
+        result
+    }
 
-        while pos != (BLOCK_SIZE - (2 * core::mem::size_of::<u64>())) {
-            pos &= BLOCK_SIZE - 1;
+    ///Writes hash of all input so far into `out`, leaving state unchanged.
+    ///
+    ///## Panics
+    ///
+    ///If `out` length differs from output size.
+    pub fn finalize_into(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.result());
+    }
 
-            if pos == 0 {
-                self.state = sha512_transform(self.state, 0, &self.buffer);
-            }
+    ///Exports current state, allowing to resume hashing later via `import_state`.
+    ///
+    ///Buffered input is included, but no other input can be recovered from it.
+    ///
+    ///## Panics
+    ///
+    ///If partial byte was hashed by `update_bits`.
+    pub fn export_state(&self) -> [u8; EXPORT_SIZE] {
+        self.export_tagged(state::SHA512)
+    }
 
-            self.buffer[pos] = 0;
-            pos += 1;
+    ///Restores state, previously exported by `export_state`.
+    pub fn import_state(input: &[u8]) -> Result<Self, StateError> {
+        Self::import_tagged(input, state::SHA512)
+    }
+
+    ///Exports current state with `algorithm` tag, used by truncated variants too.
+    pub(crate) fn export_tagged(&self, algorithm: u8) -> [u8; EXPORT_SIZE] {
+        assert!(self.bits == 0, "Cannot export state with partial byte");
+        let mut out = [0u8; EXPORT_SIZE];
+        state::write_header(&mut out, algorithm);
+
+        let (len, rest) = out[state::HEADER_SIZE..].split_at_mut(16);
+        len.copy_from_slice(&self.len.to_be_bytes());
+
+        let (words, buffer) = rest.split_at_mut(STATE_SIZE * 8);
+        for (chunk, word) in words.chunks_mut(8).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
         }
 
-        let len_lo = self.len.wrapping_shl(3).to_be_bytes();
-        let len_hi = self.len.wrapping_shr(64 - 3).to_be_bytes();
-
-        self.buffer[pos] = len_hi[0];
-        self.buffer[pos + 1] = len_hi[1];
-        self.buffer[pos + 2] = len_hi[2];
-        self.buffer[pos + 3] = len_hi[3];
-        self.buffer[pos + 4] = len_hi[4];
-        self.buffer[pos + 5] = len_hi[5];
-        self.buffer[pos + 6] = len_hi[6];
-        self.buffer[pos + 7] = len_hi[7];
-
-        self.buffer[pos + 8] = len_lo[0];
-        self.buffer[pos + 9] = len_lo[1];
-        self.buffer[pos + 10] = len_lo[2];
-        self.buffer[pos + 11] = len_lo[3];
-        self.buffer[pos + 12] = len_lo[4];
-        self.buffer[pos + 13] = len_lo[5];
-        self.buffer[pos + 14] = len_lo[6];
-        self.buffer[pos + 15] = len_lo[7];
+        let tail = (self.len & (BLOCK_SIZE as u128 - 1)) as usize;
+        buffer[..tail].copy_from_slice(&self.buffer[..tail]);
+        out
+    }
 
-        self.state = sha512_transform(self.state, 0, &self.buffer);
+    ///Restores state with `algorithm` tag, used by truncated variants too.
+    pub(crate) fn import_tagged(input: &[u8], algorithm: u8) -> Result<Self, StateError> {
+        state::check_header(input, EXPORT_SIZE, algorithm)?;
+
+        let (len, rest) = input[state::HEADER_SIZE..].split_at(16);
+        let mut result = Self::new();
+        let mut len_bytes = [0u8; 16];
+        len_bytes.copy_from_slice(len);
+        result.len = u128::from_be_bytes(len_bytes);
+        if result.len > MAX_LEN {
+            return Err(StateError::InvalidLength);
+        }
 
-        let a = self.state[0].to_be_bytes();
-        let b = self.state[1].to_be_bytes();
-        let c = self.state[2].to_be_bytes();
-        let d = self.state[3].to_be_bytes();
-        let e = self.state[4].to_be_bytes();
-        let f = self.state[5].to_be_bytes();
-        let g = self.state[6].to_be_bytes();
-        let h = self.state[7].to_be_bytes();
-        [
-            a[0], a[1], a[2], a[3], a[4], a[5], a[6], a[7],
-            b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7],
-            c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7],
-            d[0], d[1], d[2], d[3], d[4], d[5], d[6], d[7],
-            e[0], e[1], e[2], e[3], e[4], e[5], e[6], e[7],
-            f[0], f[1], f[2], f[3], f[4], f[5], f[6], f[7],
-            g[0], g[1], g[2], g[3], g[4], g[5], g[6], g[7],
-            h[0], h[1], h[2], h[3], h[4], h[5], h[6], h[7],
-        ]
+        let (words, buffer) = rest.split_at(STATE_SIZE * 8);
+        for (word, chunk) in result.state.iter_mut().zip(words.chunks(8)) {
+            let mut word_bytes = [0u8; 8];
+            word_bytes.copy_from_slice(chunk);
+            *word = u64::from_be_bytes(word_bytes);
+        }
+
+        let tail = (result.len & (BLOCK_SIZE as u128 - 1)) as usize;
+        result.buffer[..tail].copy_from_slice(&buffer[..tail]);
+        Ok(result)
     }
 }
 
@@ -421,6 +634,11 @@ impl super::Digest for Sha512 {
     type OutputType = [u8; RESULT_SIZE];
     type BlockType = [u8; BLOCK_SIZE];
 
+    const OUTPUT_SIZE: usize = RESULT_SIZE;
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+    const NAME: &'static str = "SHA-512";
+    const OID: Option<&'static [u8]> = Some(&[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03]);
+
     #[inline(always)]
     fn new() -> Self {
         Self::new()
@@ -437,7 +655,31 @@ impl super::Digest for Sha512 {
     }
 
     #[inline(always)]
-    fn result(&mut self) -> Self::OutputType {
+    fn result(&self) -> Self::OutputType {
         self.result()
     }
+
+    #[inline(always)]
+    fn finalize_reset(&mut self) -> Self::OutputType {
+        self.finalize_reset()
+    }
+
+    #[inline(always)]
+    fn finalize_into(&self, out: &mut [u8]) {
+        self.finalize_into(out);
+    }
+}
+
+impl Default for Sha512 {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Sha512 {
+    ///Writes only number of hashed bytes, without state or buffered input.
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Sha512").field("len", &self.len).finish_non_exhaustive()
+    }
 }
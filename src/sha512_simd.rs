@@ -0,0 +1,151 @@
+//!`SHA-512` message schedule via AVX2 or SSSE3.
+//!
+//!Callers must check `cpu::has_avx2` or `cpu::has_ssse3` before calling respective function.
+
+use core::arch::x86_64::*;
+
+const ROUNDS: usize = 80;
+
+#[target_feature(enable = "avx2")]
+///Computes message schedule of 128 byte `block`, 4 words at a time.
+pub(crate) unsafe fn schedule_avx2(block: &[u8]) -> [u64; ROUNDS] {
+    macro_rules! rotr {
+        ($x:expr, $n:literal) => {
+            _mm256_or_si256(_mm256_srli_epi64::<$n>($x), _mm256_slli_epi64::<{ 64 - $n }>($x))
+        };
+    }
+
+    macro_rules! s0 {
+        ($x:expr) => {
+            _mm256_xor_si256(_mm256_xor_si256(rotr!($x, 1), rotr!($x, 8)), _mm256_srli_epi64::<7>($x))
+        };
+    }
+
+    macro_rules! s1 {
+        ($x:expr) => {
+            _mm256_xor_si256(_mm256_xor_si256(rotr!($x, 19), rotr!($x, 61)), _mm256_srli_epi64::<6>($x))
+        };
+    }
+
+    //Words 1..4 of `lo` followed by word 0 of `hi`.
+    macro_rules! shift_in {
+        ($lo:expr, $hi:expr) => {
+            _mm256_permute4x64_epi64(_mm256_blend_epi32($lo, $hi, 0x03), 0x39)
+        };
+    }
+
+    assert!(block.len() >= 128, "Incomplete block");
+    //Byte swaps each 64 bit word.
+    let mask = _mm256_set_epi64x(0x0809_0A0B_0C0D_0E0F, 0x0001_0203_0405_0607, 0x0809_0A0B_0C0D_0E0F, 0x0001_0203_0405_0607);
+    let mut out = [0u64; ROUNDS];
+    let input = block.as_ptr() as *const __m256i;
+    let w = out.as_mut_ptr() as *mut __m256i;
+
+    for idx in 0..4 {
+        _mm256_storeu_si256(w.add(idx), _mm256_shuffle_epi8(_mm256_loadu_si256(input.add(idx)), mask));
+    }
+
+    for idx in 4..ROUNDS / 4 {
+        let w16 = _mm256_loadu_si256(w.add(idx - 4));
+        let w12 = _mm256_loadu_si256(w.add(idx - 3));
+        let w8 = _mm256_loadu_si256(w.add(idx - 2));
+        let w4 = _mm256_loadu_si256(w.add(idx - 1));
+
+        let partial = _mm256_add_epi64(_mm256_add_epi64(w16, s0!(shift_in!(w16, w12))), shift_in!(w8, w4));
+        //Last two words depend on first two, so sigma1 is applied in halves.
+        let lo = _mm256_add_epi64(partial, s1!(_mm256_permute4x64_epi64(w4, 0xEE)));
+        let hi = _mm256_add_epi64(partial, s1!(_mm256_permute4x64_epi64(lo, 0x44)));
+        _mm256_storeu_si256(w.add(idx), _mm256_blend_epi32(lo, hi, 0xF0));
+    }
+
+    out
+}
+
+#[target_feature(enable = "ssse3")]
+///Computes message schedule of 128 byte `block`, 2 words at a time.
+pub(crate) unsafe fn schedule_ssse3(block: &[u8]) -> [u64; ROUNDS] {
+    macro_rules! rotr {
+        ($x:expr, $n:literal) => {
+            _mm_or_si128(_mm_srli_epi64::<$n>($x), _mm_slli_epi64::<{ 64 - $n }>($x))
+        };
+    }
+
+    macro_rules! s0 {
+        ($x:expr) => {
+            _mm_xor_si128(_mm_xor_si128(rotr!($x, 1), rotr!($x, 8)), _mm_srli_epi64::<7>($x))
+        };
+    }
+
+    macro_rules! s1 {
+        ($x:expr) => {
+            _mm_xor_si128(_mm_xor_si128(rotr!($x, 19), rotr!($x, 61)), _mm_srli_epi64::<6>($x))
+        };
+    }
+
+    assert!(block.len() >= 128, "Incomplete block");
+    //Byte swaps each 64 bit word.
+    let mask = _mm_set_epi64x(0x0809_0A0B_0C0D_0E0F, 0x0001_0203_0405_0607);
+    let mut out = [0u64; ROUNDS];
+    let input = block.as_ptr() as *const __m128i;
+    let w = out.as_mut_ptr() as *mut __m128i;
+
+    for idx in 0..8 {
+        _mm_storeu_si128(w.add(idx), _mm_shuffle_epi8(_mm_loadu_si128(input.add(idx)), mask));
+    }
+
+    for idx in 8..ROUNDS / 2 {
+        let w16 = _mm_loadu_si128(w.add(idx - 8));
+        let w14 = _mm_loadu_si128(w.add(idx - 7));
+        let w8 = _mm_loadu_si128(w.add(idx - 4));
+        let w6 = _mm_loadu_si128(w.add(idx - 3));
+        let w2 = _mm_loadu_si128(w.add(idx - 1));
+
+        let next = _mm_add_epi64(w16, s0!(_mm_alignr_epi8(w14, w16, 8)));
+        let next = _mm_add_epi64(next, _mm_alignr_epi8(w6, w8, 8));
+        _mm_storeu_si128(w.add(idx), _mm_add_epi64(next, s1!(w2)));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::{schedule_avx2, schedule_ssse3};
+    use crate::sha512::sha512_schedule;
+
+    //Include words with carries across 32 bit halves and lanes.
+    fn blocks() -> [[u8; 128]; 4] {
+        let mut blocks = [[0u8; 128], [0xFF; 128], [0u8; 128], [0u8; 128]];
+        for (idx, byte) in blocks[2].iter_mut().enumerate() {
+            *byte = idx as u8;
+        }
+        for (idx, byte) in blocks[3].iter_mut().enumerate() {
+            *byte = (idx as u8).wrapping_mul(0x9D) ^ 0x5A;
+        }
+        blocks
+    }
+
+    #[test]
+    fn test_schedule_avx2() {
+        if !std::is_x86_feature_detected!("avx2") {
+            return;
+        }
+
+        for block in blocks().iter() {
+            assert_eq!(unsafe { schedule_avx2(block) }[..], sha512_schedule(0, block)[..]);
+        }
+    }
+
+    #[test]
+    fn test_schedule_ssse3() {
+        if !std::is_x86_feature_detected!("ssse3") {
+            return;
+        }
+
+        for block in blocks().iter() {
+            assert_eq!(unsafe { schedule_ssse3(block) }[..], sha512_schedule(0, block)[..]);
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//!`SHA-1` and `SHA-256` compression via x86 SHA extensions.
+//!
+//!Callers must check `cpu::has_sha_ni` before calling any function.
+
+use core::arch::x86_64::*;
+
+#[cfg(feature = "sha1")]
+#[target_feature(enable = "sha,sse2,ssse3,sse4.1")]
+///Compresses whole 64 byte blocks of `input` into `SHA-1` `state`.
+pub(crate) unsafe fn sha1_compress(state: &mut [u32; 5], input: &[u8]) {
+    macro_rules! rounds4 {
+        ($h0:ident, $h1:ident, $wk:expr, $func:expr) => {
+            _mm_sha1rnds4_epu32($h0, _mm_sha1nexte_epu32($h1, $wk), $func)
+        };
+    }
+
+    macro_rules! schedule {
+        ($w0:expr, $w1:expr, $w2:expr, $w3:expr) => {
+            _mm_sha1msg2_epu32(_mm_xor_si128(_mm_sha1msg1_epu32($w0, $w1), $w2), $w3)
+        };
+    }
+
+    macro_rules! schedule_rounds4 {
+        ($h0:ident, $h1:ident, $w0:expr, $w1:expr, $w2:expr, $w3:expr, $w4:expr, $func:expr) => {
+            $w4 = schedule!($w0, $w1, $w2, $w3);
+            $h1 = rounds4!($h0, $h1, $w4, $func);
+        };
+    }
+
+    //Reverses all bytes, placing first big endian word in highest lane.
+    let mask = _mm_set_epi64x(0x0001_0203_0405_0607, 0x0809_0A0B_0C0D_0E0F);
+    let mut abcd = _mm_set_epi32(state[0] as i32, state[1] as i32, state[2] as i32, state[3] as i32);
+    let mut e = _mm_set_epi32(state[4] as i32, 0, 0, 0);
+
+    for block in input.chunks_exact(64) {
+        let ptr = block.as_ptr() as *const __m128i;
+        let mut w0 = _mm_shuffle_epi8(_mm_loadu_si128(ptr), mask);
+        let mut w1 = _mm_shuffle_epi8(_mm_loadu_si128(ptr.add(1)), mask);
+        let mut w2 = _mm_shuffle_epi8(_mm_loadu_si128(ptr.add(2)), mask);
+        let mut w3 = _mm_shuffle_epi8(_mm_loadu_si128(ptr.add(3)), mask);
+        let mut w4;
+
+        let mut h0 = abcd;
+        let mut h1 = _mm_add_epi32(e, w0);
+
+        //Rounds 0..20
+        h1 = _mm_sha1rnds4_epu32(h0, h1, 0);
+        h0 = rounds4!(h1, h0, w1, 0);
+        h1 = rounds4!(h0, h1, w2, 0);
+        h0 = rounds4!(h1, h0, w3, 0);
+        schedule_rounds4!(h0, h1, w0, w1, w2, w3, w4, 0);
+
+        //Rounds 20..40
+        schedule_rounds4!(h1, h0, w1, w2, w3, w4, w0, 1);
+        schedule_rounds4!(h0, h1, w2, w3, w4, w0, w1, 1);
+        schedule_rounds4!(h1, h0, w3, w4, w0, w1, w2, 1);
+        schedule_rounds4!(h0, h1, w4, w0, w1, w2, w3, 1);
+        schedule_rounds4!(h1, h0, w0, w1, w2, w3, w4, 1);
+
+        //Rounds 40..60
+        schedule_rounds4!(h0, h1, w1, w2, w3, w4, w0, 2);
+        schedule_rounds4!(h1, h0, w2, w3, w4, w0, w1, 2);
+        schedule_rounds4!(h0, h1, w3, w4, w0, w1, w2, 2);
+        schedule_rounds4!(h1, h0, w4, w0, w1, w2, w3, 2);
+        schedule_rounds4!(h0, h1, w0, w1, w2, w3, w4, 2);
+
+        //Rounds 60..80
+        schedule_rounds4!(h1, h0, w1, w2, w3, w4, w0, 3);
+        schedule_rounds4!(h0, h1, w2, w3, w4, w0, w1, 3);
+        schedule_rounds4!(h1, h0, w3, w4, w0, w1, w2, 3);
+        schedule_rounds4!(h0, h1, w4, w0, w1, w2, w3, 3);
+        schedule_rounds4!(h1, h0, w0, w1, w2, w3, w4, 3);
+
+        abcd = _mm_add_epi32(abcd, h0);
+        e = _mm_sha1nexte_epu32(h1, e);
+    }
+
+    state[0] = _mm_extract_epi32(abcd, 3) as u32;
+    state[1] = _mm_extract_epi32(abcd, 2) as u32;
+    state[2] = _mm_extract_epi32(abcd, 1) as u32;
+    state[3] = _mm_extract_epi32(abcd, 0) as u32;
+    state[4] = _mm_extract_epi32(e, 3) as u32;
+}
+
+#[cfg(feature = "sha256")]
+#[target_feature(enable = "sha,sse2,ssse3,sse4.1")]
+///Compresses whole 64 byte blocks of `input` into `SHA-256` `state`.
+pub(crate) unsafe fn sha256_compress(state: &mut [u32; 8], input: &[u8]) {
+    use crate::sha256::K256;
+
+    //Byte swaps each 32 bit word.
+    let mask = _mm_set_epi64x(0x0C0D_0E0F_0809_0A0B, 0x0405_0607_0001_0203);
+
+    //Instructions operate on state split as ABEF and CDGH.
+    let ptr = state.as_ptr() as *const __m128i;
+    let dcba = _mm_loadu_si128(ptr);
+    let hgfe = _mm_loadu_si128(ptr.add(1));
+    let cdab = _mm_shuffle_epi32(dcba, 0xB1);
+    let efgh = _mm_shuffle_epi32(hgfe, 0x1B);
+    let mut abef = _mm_alignr_epi8(cdab, efgh, 8);
+    let mut cdgh = _mm_blend_epi16(efgh, cdab, 0xF0);
+
+    for block in input.chunks_exact(64) {
+        let abef_save = abef;
+        let cdgh_save = cdgh;
+
+        let ptr = block.as_ptr() as *const __m128i;
+        let mut w = [
+            _mm_shuffle_epi8(_mm_loadu_si128(ptr), mask),
+            _mm_shuffle_epi8(_mm_loadu_si128(ptr.add(1)), mask),
+            _mm_shuffle_epi8(_mm_loadu_si128(ptr.add(2)), mask),
+            _mm_shuffle_epi8(_mm_loadu_si128(ptr.add(3)), mask),
+        ];
+
+        //Message schedule keeps last 16 words, 4 per vector.
+        for idx in 0..16 {
+            if idx >= 4 {
+                let w0 = w[idx % 4];
+                let w1 = w[(idx + 1) % 4];
+                let w2 = w[(idx + 2) % 4];
+                let w3 = w[(idx + 3) % 4];
+                let next = _mm_add_epi32(_mm_sha256msg1_epu32(w0, w1), _mm_alignr_epi8(w3, w2, 4));
+                w[idx % 4] = _mm_sha256msg2_epu32(next, w3);
+            }
+
+            let wk = _mm_add_epi32(w[idx % 4], _mm_loadu_si128(K256.as_ptr().add(idx * 4) as *const __m128i));
+            cdgh = _mm_sha256rnds2_epu32(cdgh, abef, wk);
+            abef = _mm_sha256rnds2_epu32(abef, cdgh, _mm_shuffle_epi32(wk, 0x0E));
+        }
+
+        abef = _mm_add_epi32(abef, abef_save);
+        cdgh = _mm_add_epi32(cdgh, cdgh_save);
+    }
+
+    let feba = _mm_shuffle_epi32(abef, 0x1B);
+    let dchg = _mm_shuffle_epi32(cdgh, 0xB1);
+    let dcba = _mm_blend_epi16(feba, dchg, 0xF0);
+    let hgfe = _mm_alignr_epi8(dchg, feba, 8);
+
+    let ptr = state.as_mut_ptr() as *mut __m128i;
+    _mm_storeu_si128(ptr, dcba);
+    _mm_storeu_si128(ptr.add(1), hgfe);
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    //Include words with carries across lanes.
+    fn blocks() -> [[u8; 64]; 4] {
+        let mut blocks = [[0u8; 64], [0xFF; 64], [0u8; 64], [0u8; 64]];
+        for (idx, byte) in blocks[2].iter_mut().enumerate() {
+            *byte = idx as u8;
+        }
+        for (idx, byte) in blocks[3].iter_mut().enumerate() {
+            *byte = (idx as u8).wrapping_mul(0x9D) ^ 0x5A;
+        }
+        blocks
+    }
+
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn test_sha1_compress() {
+        use crate::Sha1State;
+
+        if !std::is_x86_feature_detected!("sha") {
+            return;
+        }
+
+        let blocks = blocks();
+        let mut state = Sha1State::new();
+        unsafe {
+            super::sha1_compress(&mut state.0, blocks.concat().as_slice());
+        }
+        assert_eq!(state, blocks.iter().fold(Sha1State::new(), |state, block| state.compress(block)));
+    }
+
+    #[cfg(feature = "sha256")]
+    #[test]
+    fn test_sha256_compress() {
+        use crate::Sha256State;
+
+        if !std::is_x86_feature_detected!("sha") {
+            return;
+        }
+
+        let blocks = blocks();
+        let mut state = Sha256State::new();
+        unsafe {
+            super::sha256_compress(&mut state.0, blocks.concat().as_slice());
+        }
+        assert_eq!(state, blocks.iter().fold(Sha256State::new(), |state, block| state.compress(block)));
+    }
+}
@@ -0,0 +1,389 @@
+//!AWS Signature Version 4 request signing.
+//!
+//!Canonical request is hashed as it is produced, so no intermediate buffers are required.
+//!Headers and query parameters are sorted during iteration, which is quadratic, but fine for usual requests.
+
+use crate::{hmac, DigestFmt, HmacKey, Sha256};
+
+use core::cmp::Ordering;
+use core::fmt::{self, Write};
+
+///Signing algorithm identifier.
+pub const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+///Payload hash for requests with unsigned payload (S3 only).
+pub const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+const TERMINATOR: &str = "aws4_request";
+const TIMESTAMP_SIZE: usize = 16;
+const DATE_SIZE: usize = 8;
+const HEX: &[u8; 16] = b"0123456789ABCDEF";
+const HEX_LOWER: &[u8; 16] = b"0123456789abcdef";
+
+///Request to sign.
+pub struct Request<'a> {
+    ///HTTP method.
+    pub method: &'a str,
+    ///URI path, as it appears in request line, i.e. already percent-encoded and normalized.
+    ///
+    ///It is encoded once more for all services except `s3`.
+    pub path: &'a str,
+    ///Query parameters, not encoded.
+    pub query: &'a [(&'a str, &'a str)],
+    ///Headers to sign, which must include `host` and `x-amz-date`.
+    ///
+    ///Repeated headers are combined in order of appearance.
+    pub headers: &'a [(&'a str, &'a str)],
+    ///Hex encoded SHA-256 of payload, or `UNSIGNED_PAYLOAD`.
+    pub payload_hash: &'a str,
+}
+
+///Destination of canonical request and string to sign.
+trait Sink {
+    fn write(&mut self, data: &[u8]) -> Option<()>;
+}
+
+impl Sink for Sha256 {
+    #[inline(always)]
+    fn write(&mut self, data: &[u8]) -> Option<()> {
+        self.update(data);
+        Some(())
+    }
+}
+
+struct Buffer<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> Buffer<'a> {
+    #[inline]
+    fn new(buf: &'a mut [u8]) -> Self {
+        Self {
+            buf,
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn into_str(self) -> Option<&'a str> {
+        core::str::from_utf8(&self.buf[..self.len]).ok()
+    }
+}
+
+impl Sink for Buffer<'_> {
+    fn write(&mut self, data: &[u8]) -> Option<()> {
+        let end = self.len + data.len();
+        self.buf.get_mut(self.len..end)?.copy_from_slice(data);
+        self.len = end;
+        Some(())
+    }
+}
+
+impl fmt::Write for Buffer<'_> {
+    #[inline]
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.write(text.as_bytes()).ok_or(fmt::Error)
+    }
+}
+
+#[inline]
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'.' || byte == b'_' || byte == b'~'
+}
+
+///Writes URI encoded `input`, keeping `/` if `keep_slash` is set.
+fn write_encoded<S: Sink>(sink: &mut S, input: &str, keep_slash: bool) -> Option<()> {
+    for byte in input.bytes() {
+        if is_unreserved(byte) || (keep_slash && byte == b'/') {
+            sink.write(&[byte])?;
+        } else {
+            sink.write(&[b'%', HEX[(byte >> 4) as usize], HEX[(byte & 0xf) as usize]])?;
+        }
+    }
+    Some(())
+}
+
+///Compares strings by their URI encoded form, without encoding them.
+///
+///Encoded byte starts with `%`, which precedes any unreserved character,
+///while encoded bytes are ordered as bytes themselves.
+fn cmp_encoded(left: &str, right: &str) -> Ordering {
+    let key = |byte: u8| match is_unreserved(byte) {
+        true => 0x100 | byte as u16,
+        false => byte as u16,
+    };
+
+    left.bytes().map(key).cmp(right.bytes().map(key))
+}
+
+fn cmp_query(query: &[(&str, &str)], left: usize, right: usize) -> Ordering {
+    cmp_encoded(query[left].0, query[right].0).then_with(|| cmp_encoded(query[left].1, query[right].1))
+                                              .then(left.cmp(&right))
+}
+
+///Iterates over query parameters in canonical order, returning their indexes.
+fn sorted_query<'a>(query: &'a [(&'a str, &'a str)]) -> impl Iterator<Item = usize> + 'a {
+    let mut prev: Option<usize> = None;
+    core::iter::from_fn(move || {
+        let mut best: Option<usize> = None;
+        for idx in 0..query.len() {
+            let is_next = match prev {
+                Some(prev) => cmp_query(query, idx, prev) == Ordering::Greater,
+                None => true,
+            };
+            let is_best = match best {
+                Some(best) => cmp_query(query, idx, best) == Ordering::Less,
+                None => true,
+            };
+            if is_next && is_best {
+                best = Some(idx);
+            }
+        }
+        prev = best;
+        best
+    })
+}
+
+fn cmp_name(left: &str, right: &str) -> Ordering {
+    let left = left.trim().bytes().map(|byte| byte.to_ascii_lowercase());
+    let right = right.trim().bytes().map(|byte| byte.to_ascii_lowercase());
+    left.cmp(right)
+}
+
+///Iterates over unique header names in canonical order, returning index of first occurrence.
+fn sorted_headers<'a>(headers: &'a [(&'a str, &'a str)]) -> impl Iterator<Item = usize> + 'a {
+    let mut prev: Option<usize> = None;
+    core::iter::from_fn(move || {
+        let mut best: Option<usize> = None;
+        for (idx, (name, _)) in headers.iter().enumerate() {
+            let is_next = match prev {
+                Some(prev) => cmp_name(name, headers[prev].0) == Ordering::Greater,
+                None => true,
+            };
+            let is_best = match best {
+                Some(best) => cmp_name(name, headers[best].0) == Ordering::Less,
+                None => true,
+            };
+            if is_next && is_best {
+                best = Some(idx);
+            }
+        }
+        prev = best;
+        best
+    })
+}
+
+fn write_lowercase<S: Sink>(sink: &mut S, name: &str) -> Option<()> {
+    for byte in name.trim().bytes() {
+        sink.write(&[byte.to_ascii_lowercase()])?;
+    }
+    Some(())
+}
+
+fn write_signed_headers<S: Sink>(sink: &mut S, headers: &[(&str, &str)]) -> Option<()> {
+    for (count, idx) in sorted_headers(headers).enumerate() {
+        if count > 0 {
+            sink.write(b";")?;
+        }
+        write_lowercase(sink, headers[idx].0)?;
+    }
+    Some(())
+}
+
+fn write_canonical_request<S: Sink>(sink: &mut S, request: &Request<'_>, double_encode: bool) -> Option<()> {
+    sink.write(request.method.as_bytes())?;
+    sink.write(b"\n")?;
+
+    match (request.path.is_empty(), double_encode) {
+        (true, _) => sink.write(b"/")?,
+        (false, true) => write_encoded(sink, request.path, true)?,
+        (false, false) => sink.write(request.path.as_bytes())?,
+    }
+    sink.write(b"\n")?;
+
+    for (count, idx) in sorted_query(request.query).enumerate() {
+        if count > 0 {
+            sink.write(b"&")?;
+        }
+        write_encoded(sink, request.query[idx].0, false)?;
+        sink.write(b"=")?;
+        write_encoded(sink, request.query[idx].1, false)?;
+    }
+    sink.write(b"\n")?;
+
+    let headers = request.headers;
+    for idx in sorted_headers(headers) {
+        write_lowercase(sink, headers[idx].0)?;
+        sink.write(b":")?;
+
+        let values = headers[idx..].iter().filter(|(name, _)| cmp_name(name, headers[idx].0) == Ordering::Equal);
+        for (count, (_, value)) in values.enumerate() {
+            if count > 0 {
+                sink.write(b",")?;
+            }
+            for (count, part) in value.split_ascii_whitespace().enumerate() {
+                if count > 0 {
+                    sink.write(b" ")?;
+                }
+                sink.write(part.as_bytes())?;
+            }
+        }
+        sink.write(b"\n")?;
+    }
+    sink.write(b"\n")?;
+
+    write_signed_headers(sink, headers)?;
+    sink.write(b"\n")?;
+    sink.write(request.payload_hash.as_bytes())
+}
+
+fn write_hex(hash: &[u8; 32], out: &mut [u8; 64]) {
+    for (byte, hex) in hash.iter().zip(out.chunks_mut(2)) {
+        hex[0] = HEX_LOWER[(byte >> 4) as usize];
+        hex[1] = HEX_LOWER[(byte & 0xf) as usize];
+    }
+}
+
+///Derives signing key `HMAC(HMAC(HMAC(HMAC("AWS4" + secret, date), region), service), "aws4_request")`.
+///
+///- `secret_key` - Secret access key.
+///- `date` - Date in `YYYYMMDD` format.
+///- `region` - Region, e.g. `us-east-1`.
+///- `service` - Service, e.g. `s3`.
+pub fn signing_key(secret_key: &str, date: &str, region: &str, service: &str) -> [u8; 32] {
+    const PREFIX: &[u8] = b"AWS4";
+
+    //Concatenate in place, hashing it if it is longer than block, as HMAC does.
+    let mut key = [0u8; 64];
+    let mut len = PREFIX.len() + secret_key.len();
+    if len <= key.len() {
+        key[..PREFIX.len()].copy_from_slice(PREFIX);
+        key[PREFIX.len()..len].copy_from_slice(secret_key.as_bytes());
+    } else {
+        let mut algo = Sha256::new();
+        algo.update(PREFIX);
+        algo.update(secret_key.as_bytes());
+        key[..32].copy_from_slice(&algo.result());
+        len = 32;
+    }
+
+    let key = hmac::<Sha256>(date.as_bytes(), &key[..len]);
+    let key = hmac::<Sha256>(region.as_bytes(), &key);
+    let key = hmac::<Sha256>(service.as_bytes(), &key);
+    hmac::<Sha256>(TERMINATOR.as_bytes(), &key)
+}
+
+///Writes hex encoded SHA-256 of `payload` into `out`, to be used as `Request::payload_hash`.
+pub fn hash_payload<'a>(payload: &[u8], out: &'a mut [u8; 64]) -> &'a str {
+    let mut algo = Sha256::new();
+    algo.update(payload);
+    write_hex(&algo.result(), out);
+
+    //Only hex digits are written
+    unsafe {
+        core::str::from_utf8_unchecked(out)
+    }
+}
+
+///Request signer.
+pub struct Signer<'a> {
+    ///Access key ID.
+    pub access_key: &'a str,
+    ///Secret access key.
+    pub secret_key: &'a str,
+    ///Region, e.g. `us-east-1`.
+    pub region: &'a str,
+    ///Service, e.g. `s3`.
+    pub service: &'a str,
+    ///Request time in `YYYYMMDD'T'HHMMSS'Z'` format, which must match `x-amz-date` header.
+    pub timestamp: &'a str,
+}
+
+impl<'a> Signer<'a> {
+    fn date(&self) -> &str {
+        assert!(self.timestamp.len() == TIMESTAMP_SIZE && self.timestamp.is_char_boundary(DATE_SIZE), "Timestamp must be in YYYYMMDD'T'HHMMSS'Z' format");
+        &self.timestamp[..DATE_SIZE]
+    }
+
+    #[inline]
+    fn double_encode(&self) -> bool {
+        self.service != "s3"
+    }
+
+    ///Writes canonical request into `out`.
+    ///
+    ///Returns `None` if `out` is too small.
+    pub fn canonical_request<'b>(&self, request: &Request<'_>, out: &'b mut [u8]) -> Option<&'b str> {
+        let mut buffer = Buffer::new(out);
+        write_canonical_request(&mut buffer, request, self.double_encode())?;
+        buffer.into_str()
+    }
+
+    ///Calls `cb` with parts of string to sign.
+    fn with_string_to_sign<R>(&self, request: &Request<'_>, cb: impl FnOnce(&[&[u8]]) -> R) -> R {
+        let mut algo = Sha256::new();
+        //Hashing never fails
+        let _ = write_canonical_request(&mut algo, request, self.double_encode());
+
+        let mut hash = [0u8; 64];
+        write_hex(&algo.result(), &mut hash);
+
+        cb(&[
+            ALGORITHM.as_bytes(), b"\n",
+            self.timestamp.as_bytes(), b"\n",
+            self.date().as_bytes(), b"/", self.region.as_bytes(), b"/", self.service.as_bytes(), b"/", TERMINATOR.as_bytes(), b"\n",
+            &hash,
+        ])
+    }
+
+    ///Writes string to sign into `out`.
+    ///
+    ///Returns `None` if `out` is too small.
+    ///
+    ///## Panics
+    ///
+    ///If `timestamp` is not in `YYYYMMDD'T'HHMMSS'Z'` format.
+    pub fn string_to_sign<'b>(&self, request: &Request<'_>, out: &'b mut [u8]) -> Option<&'b str> {
+        let mut buffer = Buffer::new(out);
+        self.with_string_to_sign(request, |parts| parts.iter().try_for_each(|part| buffer.write(part)))?;
+        buffer.into_str()
+    }
+
+    #[inline]
+    ///Derives signing key for `secret_key`, date of `timestamp`, `region` and `service`.
+    ///
+    ///Key is valid for the whole day, so it can be cached.
+    ///
+    ///## Panics
+    ///
+    ///If `timestamp` is not in `YYYYMMDD'T'HHMMSS'Z'` format.
+    pub fn signing_key(&self) -> [u8; 32] {
+        signing_key(self.secret_key, self.date(), self.region, self.service)
+    }
+
+    ///Computes request signature.
+    ///
+    ///## Panics
+    ///
+    ///If `timestamp` is not in `YYYYMMDD'T'HHMMSS'Z'` format.
+    pub fn signature(&self, request: &Request<'_>) -> [u8; 32] {
+        let key = HmacKey::<Sha256>::new(&self.signing_key());
+        self.with_string_to_sign(request, |parts| key.sign_parts(parts))
+    }
+
+    ///Writes `Authorization` header value into `out`.
+    ///
+    ///Returns `None` if `out` is too small.
+    ///
+    ///## Panics
+    ///
+    ///If `timestamp` is not in `YYYYMMDD'T'HHMMSS'Z'` format.
+    pub fn authorization<'b>(&self, request: &Request<'_>, out: &'b mut [u8]) -> Option<&'b str> {
+        let signature = self.signature(request);
+
+        let mut buffer = Buffer::new(out);
+        write!(buffer, "{} Credential={}/{}/{}/{}/{}, SignedHeaders=", ALGORITHM, self.access_key, self.date(), self.region, self.service, TERMINATOR).ok()?;
+        write_signed_headers(&mut buffer, request.headers)?;
+        write!(buffer, ", Signature={}", DigestFmt(signature)).ok()?;
+        buffer.into_str()
+    }
+}
@@ -0,0 +1,71 @@
+//!Exported hasher state format.
+//!
+//!- Format version, 1 byte.
+//!- Algorithm tag, 1 byte.
+//!- Length of hashed input in bytes, big endian (8 bytes, or 16 bytes for `SHA-512` family).
+//!- State words, big endian.
+//!- Buffered input of incomplete block, padded with zeros to block size.
+
+use core::fmt;
+
+///Current format version.
+const VERSION: u8 = 1;
+
+#[cfg(feature = "md5")]
+pub(crate) const MD5: u8 = 1;
+#[cfg(feature = "sha1")]
+pub(crate) const SHA1: u8 = 2;
+#[cfg(feature = "sha256")]
+pub(crate) const SHA256: u8 = 3;
+#[cfg(feature = "sha512")]
+pub(crate) const SHA512: u8 = 4;
+#[cfg(feature = "sha384")]
+pub(crate) const SHA384: u8 = 5;
+#[cfg(feature = "sha512_256")]
+pub(crate) const SHA512_256: u8 = 6;
+
+///Size of header, preceding length.
+pub(crate) const HEADER_SIZE: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///Hasher state import error
+pub enum StateError {
+    ///Exported state has invalid size.
+    InvalidSize,
+    ///Exported state has unsupported format version.
+    UnsupportedVersion,
+    ///Exported state belongs to different algorithm.
+    AlgorithmMismatch,
+    ///Length of hashed input is out of range.
+    InvalidLength,
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateError::InvalidSize => fmt.write_str("Hasher state has invalid size"),
+            StateError::UnsupportedVersion => fmt.write_str("Hasher state has unsupported version"),
+            StateError::AlgorithmMismatch => fmt.write_str("Hasher state belongs to different algorithm"),
+            StateError::InvalidLength => fmt.write_str("Hasher state length is out of range"),
+        }
+    }
+}
+
+///Writes header of `algorithm` into `out`.
+pub(crate) fn write_header(out: &mut [u8], algorithm: u8) {
+    out[0] = VERSION;
+    out[1] = algorithm;
+}
+
+///Checks that `input` is exported state of `algorithm` with expected `size`.
+pub(crate) fn check_header(input: &[u8], size: usize, algorithm: u8) -> Result<(), StateError> {
+    if input.len() != size {
+        return Err(StateError::InvalidSize);
+    } else if input[0] != VERSION {
+        return Err(StateError::UnsupportedVersion);
+    } else if input[1] != algorithm {
+        return Err(StateError::AlgorithmMismatch);
+    }
+
+    Ok(())
+}
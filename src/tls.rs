@@ -0,0 +1,92 @@
+//!TLS key derivation helpers
+//!
+//!- TLS 1.2 `PRF` over any `Digest`, i.e. `prf::<Sha256>` is `P_SHA256` and `prf::<Sha384>` is `P_SHA384`.
+//!- TLS 1.0/1.1 `PRF` combining `P_MD5` and `P_SHA1` (requires `md5` and `sha1` features).
+//!- TLS 1.3 `HKDF-Expand-Label` and `Derive-Secret`.
+//!
+//!`HKDF-Extract` steps of TLS 1.3 key schedule are plain `hmac` keyed with salt.
+
+use crate::{Digest, HmacKey};
+use crate::hkdf::hkdf_expand;
+
+///Maximum size of `HkdfLabel` structure.
+const MAX_HKDF_LABEL_SIZE: usize = 2 + 1 + 255 + 1 + 255;
+const TLS13_LABEL_PREFIX: &[u8] = b"tls13 ";
+
+///`P_hash` data expansion function, XORing its output into `out`.
+fn p_hash_xor<D: Digest>(secret: &[u8], label: &[u8], seed: &[u8], out: &mut [u8]) {
+    let key = HmacKey::<D>::new(secret);
+    let mut a = key.sign_parts(&[label, seed]);
+
+    let hash_len = a.as_ref().len();
+    for chunk in out.chunks_mut(hash_len) {
+        let block = key.sign_parts(&[a.as_ref(), label, seed]);
+        for (byte, block_byte) in chunk.iter_mut().zip(block.as_ref().iter()) {
+            *byte ^= block_byte;
+        }
+        a = key.sign(a.as_ref());
+    }
+}
+
+///TLS 1.2 `PRF(secret, label, seed)` filling `out`, as specified by RFC 5246.
+///
+///Algorithm is defined by `Digest`, which is `Sha256` unless cipher suite specifies otherwise.
+pub fn prf<D: Digest>(secret: &[u8], label: &[u8], seed: &[u8], out: &mut [u8]) {
+    for byte in out.iter_mut() {
+        *byte = 0;
+    }
+    p_hash_xor::<D>(secret, label, seed, out);
+}
+
+#[cfg(all(feature = "md5", feature = "sha1"))]
+///TLS 1.0 and 1.1 `PRF(secret, label, seed)` filling `out`, as specified by RFC 2246.
+///
+///Output is `P_MD5` over first half of `secret`, XORed with `P_SHA1` over second half.
+pub fn prf_legacy(secret: &[u8], label: &[u8], seed: &[u8], out: &mut [u8]) {
+    let half = secret.len() - secret.len() / 2;
+
+    for byte in out.iter_mut() {
+        *byte = 0;
+    }
+    p_hash_xor::<crate::Md5>(&secret[..half], label, seed, out);
+    p_hash_xor::<crate::Sha1>(&secret[secret.len() - half..], label, seed, out);
+}
+
+///TLS 1.3 `HKDF-Expand-Label(secret, label, context, out.len())`, as specified by RFC 8446.
+///
+///`label` is provided without `"tls13 "` prefix.
+///
+///## Panics
+///
+///- If `label` is longer than 249 bytes or `context` is longer than 255 bytes.
+///- If `out` is longer than `u16::MAX` or HKDF limit.
+pub fn hkdf_expand_label<D: Digest>(secret: &[u8], label: &[u8], context: &[u8], out: &mut [u8]) {
+    let label_len = TLS13_LABEL_PREFIX.len() + label.len();
+    assert!(label_len <= u8::MAX as usize, "Label is too long");
+    assert!(context.len() <= u8::MAX as usize, "Context is too long");
+    assert!(out.len() <= u16::MAX as usize, "Output is too long");
+
+    let mut hkdf_label = [0u8; MAX_HKDF_LABEL_SIZE];
+    hkdf_label[..2].copy_from_slice(&(out.len() as u16).to_be_bytes());
+    hkdf_label[2] = label_len as u8;
+    hkdf_label[3..3 + TLS13_LABEL_PREFIX.len()].copy_from_slice(TLS13_LABEL_PREFIX);
+    hkdf_label[3 + TLS13_LABEL_PREFIX.len()..3 + label_len].copy_from_slice(label);
+    let mut cursor = 3 + label_len;
+    hkdf_label[cursor] = context.len() as u8;
+    cursor += 1;
+    hkdf_label[cursor..cursor + context.len()].copy_from_slice(context);
+    cursor += context.len();
+
+    hkdf_expand::<D>(secret, &hkdf_label[..cursor], out);
+}
+
+///TLS 1.3 `Derive-Secret(secret, label, messages)`, as specified by RFC 8446.
+///
+///`transcript_hash` is `Transcript-Hash(messages)`, computed with the same `Digest`.
+pub fn derive_secret<D: Digest>(secret: &[u8], label: &[u8], transcript_hash: &[u8]) -> D::OutputType {
+    let mut result: D::OutputType = unsafe {
+        core::mem::MaybeUninit::zeroed().assume_init()
+    };
+    hkdf_expand_label::<D>(secret, label, transcript_hash, result.as_mut());
+    result
+}
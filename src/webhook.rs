@@ -0,0 +1,160 @@
+//!Webhook signature verification.
+//!
+//!- GitHub `X-Hub-Signature-256` and legacy `X-Hub-Signature` (requires `sha1` feature).
+//!- Stripe `Stripe-Signature`.
+//!- Slack `X-Slack-Signature` with `X-Slack-Request-Timestamp`.
+//!
+//!Current time is provided by user as seconds since UNIX epoch.
+//!Signatures are compared in constant time.
+
+use crate::{ct_eq, Digest, HmacKey, Sha256};
+
+use core::fmt;
+
+///Default timestamp tolerance in seconds, as used by Stripe and Slack SDKs.
+pub const DEFAULT_TOLERANCE: u64 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///Webhook verification error
+pub enum WebhookError {
+    ///Header is malformed or uses unsupported scheme.
+    InvalidHeader,
+    ///Signature doesn't match.
+    InvalidSignature,
+    ///Timestamp is outside of tolerance.
+    TimestampOutOfRange,
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebhookError::InvalidHeader => fmt.write_str("Webhook signature header is malformed"),
+            WebhookError::InvalidSignature => fmt.write_str("Webhook signature is invalid"),
+            WebhookError::TimestampOutOfRange => fmt.write_str("Webhook timestamp is outside of tolerance"),
+        }
+    }
+}
+
+///Decodes single hex digit.
+const fn hex_digit(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+///Decodes hex `signature` of digest's output size and compares it with `expected`.
+fn verify_hex<D: Digest>(signature: &str, expected: &D::OutputType) -> Result<(), WebhookError> {
+    let mut actual: D::OutputType = unsafe {
+        core::mem::MaybeUninit::zeroed().assume_init()
+    };
+    let actual_bytes = actual.as_mut();
+    if signature.len() != actual_bytes.len() * 2 {
+        return Err(WebhookError::InvalidHeader);
+    }
+
+    for (byte, hex) in actual_bytes.iter_mut().zip(signature.as_bytes().chunks(2)) {
+        *byte = match (hex_digit(hex[0]), hex_digit(hex[1])) {
+            (Some(high), Some(low)) => high << 4 | low,
+            _ => return Err(WebhookError::InvalidHeader),
+        };
+    }
+
+    match ct_eq(actual.as_ref(), expected.as_ref()) {
+        true => Ok(()),
+        false => Err(WebhookError::InvalidSignature),
+    }
+}
+
+///Parses timestamp and checks that it is within `tolerance` seconds of `now`.
+fn check_timestamp(timestamp: &str, now: u64, tolerance: u64) -> Result<(), WebhookError> {
+    if timestamp.is_empty() || !timestamp.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(WebhookError::InvalidHeader);
+    }
+
+    let timestamp = match timestamp.parse::<u64>() {
+        Ok(timestamp) => timestamp,
+        Err(_) => return Err(WebhookError::InvalidHeader),
+    };
+
+    let diff = match now > timestamp {
+        true => now - timestamp,
+        false => timestamp - now,
+    };
+    match diff <= tolerance {
+        true => Ok(()),
+        false => Err(WebhookError::TimestampOutOfRange),
+    }
+}
+
+///Verifies GitHub's `X-Hub-Signature-256` header value `sha256=<hex>` over raw request `body`.
+///
+///Legacy `X-Hub-Signature` value `sha1=<hex>` is accepted only with `sha1` feature.
+pub fn verify_github(secret: &[u8], header: &str, body: &[u8]) -> Result<(), WebhookError> {
+    if let Some(signature) = header.strip_prefix("sha256=") {
+        return verify_hex::<Sha256>(signature, &HmacKey::<Sha256>::new(secret).sign(body));
+    }
+
+    #[cfg(feature = "sha1")]
+    {
+        if let Some(signature) = header.strip_prefix("sha1=") {
+            return verify_hex::<crate::Sha1>(signature, &HmacKey::<crate::Sha1>::new(secret).sign(body));
+        }
+    }
+
+    Err(WebhookError::InvalidHeader)
+}
+
+///Verifies Stripe's `Stripe-Signature` header value `t=<timestamp>,v1=<hex>[,...]` over raw request `body`.
+///
+///Signature is valid if any of `v1` signatures matches, other schemes are ignored.
+///
+///- `now` - Current time in seconds since UNIX epoch.
+///- `tolerance` - Maximum difference between `now` and `t` in seconds.
+pub fn verify_stripe(secret: &[u8], header: &str, body: &[u8], now: u64, tolerance: u64) -> Result<(), WebhookError> {
+    let mut timestamp = None;
+    for part in header.split(',') {
+        if let Some(value) = part.trim().strip_prefix("t=") {
+            if timestamp.is_some() {
+                return Err(WebhookError::InvalidHeader);
+            }
+            timestamp = Some(value);
+        }
+    }
+
+    let timestamp = match timestamp {
+        Some(timestamp) => timestamp,
+        None => return Err(WebhookError::InvalidHeader),
+    };
+    check_timestamp(timestamp, now, tolerance)?;
+
+    let expected = HmacKey::<Sha256>::new(secret).sign_parts(&[timestamp.as_bytes(), b".", body]);
+    let mut result = Err(WebhookError::InvalidHeader);
+    for signature in header.split(',').filter_map(|part| part.trim().strip_prefix("v1=")) {
+        result = match verify_hex::<Sha256>(signature, &expected) {
+            Ok(()) => return Ok(()),
+            Err(WebhookError::InvalidHeader) => result,
+            Err(error) => Err(error),
+        };
+    }
+
+    result
+}
+
+///Verifies Slack's `X-Slack-Signature` header value `v0=<hex>` over raw request `body`.
+///
+///- `timestamp` - `X-Slack-Request-Timestamp` header value.
+///- `now` - Current time in seconds since UNIX epoch.
+///- `tolerance` - Maximum difference between `now` and `timestamp` in seconds.
+pub fn verify_slack(secret: &[u8], timestamp: &str, signature: &str, body: &[u8], now: u64, tolerance: u64) -> Result<(), WebhookError> {
+    let signature = match signature.strip_prefix("v0=") {
+        Some(signature) => signature,
+        None => return Err(WebhookError::InvalidHeader),
+    };
+    check_timestamp(timestamp, now, tolerance)?;
+
+    let expected = HmacKey::<Sha256>::new(secret).sign_parts(&[b"v0:", timestamp.as_bytes(), b":", body]);
+    verify_hex::<Sha256>(signature, &expected)
+}
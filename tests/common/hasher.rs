@@ -0,0 +1,150 @@
+//!Tests shared by hasher implementations.
+//!
+//!Each macro expands to `#[test]` function and expects `lhash::Digest` to be in scope.
+#![allow(unused_macros)]
+
+///Checks `Digest` metadata: `test_metadata!(Hasher, OUTPUT_SIZE, BLOCK_SIZE, NAME, OID)`.
+macro_rules! test_metadata {
+    ($hasher:ident, $output:expr, $block:expr, $name:expr, $oid:expr) => {
+        #[test]
+        fn test_metadata() {
+            const OUTPUT: [u8; $hasher::OUTPUT_SIZE] = [0; $hasher::OUTPUT_SIZE];
+
+            assert_eq!(OUTPUT.len(), $hasher::new().result().len());
+            assert_eq!($hasher::OUTPUT_SIZE, $output);
+            assert_eq!($hasher::BLOCK_SIZE, $block);
+            assert_eq!($hasher::NAME, $name);
+            assert_eq!($hasher::OID, Some(&$oid[..]));
+        }
+    };
+}
+
+///Checks non-destructive `result`, `finalize_into` and `finalize_reset`: `test_finalize!(Hasher, oneshot_fn)`.
+macro_rules! test_finalize {
+    ($hasher:ident, $oneshot:path) => {
+        #[test]
+        fn test_finalize() {
+            let data = [0x5Au8; 300];
+            let expected = $oneshot(&data);
+
+            let mut hasher = $hasher::new();
+            hasher.update(&data[..100]);
+            let partial = hasher.result();
+            assert_eq!(partial, hasher.result());
+            assert_eq!(partial, $oneshot(&data[..100]));
+
+            hasher.update(&data[100..]);
+            assert_eq!(hasher.result(), expected);
+
+            let mut out = [0u8; $hasher::OUTPUT_SIZE];
+            hasher.finalize_into(&mut out);
+            assert_eq!(out, expected);
+
+            assert_eq!(hasher.finalize_reset(), expected);
+            assert_eq!(hasher.result(), $oneshot(b""));
+        }
+    };
+}
+
+///Checks `Default`, `Copy` and redacted `Debug`: `test_traits!(Hasher, oneshot_fn)`.
+macro_rules! test_traits {
+    ($hasher:ident, $oneshot:path) => {
+        #[test]
+        fn test_traits() {
+            let mut transcript = $hasher::default();
+            transcript.update(b"ClientHello");
+
+            //Fork transcript mid-way, continuing original
+            let snapshot = transcript;
+            transcript.update(b"ServerHello");
+
+            assert_eq!(snapshot.result(), $oneshot(b"ClientHello"));
+            assert_eq!(transcript.result(), $oneshot(b"ClientHelloServerHello"));
+
+            assert_eq!(format!("{:?}", transcript), concat!(stringify!($hasher), " { len: 22, .. }"));
+        }
+    };
+}
+
+///Checks `export_state` round trip at block boundaries: `test_export_state!(Hasher, oneshot_fn, EXPORT_SIZE)`.
+macro_rules! test_export_state {
+    ($hasher:ident, $oneshot:path, $size:expr) => {
+        #[test]
+        fn test_export_state() {
+            let data = [0xC3u8; 1000];
+
+            for split in [0, 1, 63, 64, 65, 127, 128, 129, 999].iter() {
+                let mut hasher = $hasher::new();
+                hasher.update(&data[..*split]);
+
+                let exported = hasher.export_state();
+                assert_eq!(exported.len(), $size);
+                let mut resumed = $hasher::import_state(&exported).expect("to import");
+                resumed.update(&data[*split..]);
+                assert_eq!(resumed.result(), $oneshot(&data));
+            }
+        }
+    };
+}
+
+///Checks `compress_blocks` of hasher and raw state: `test_compress_blocks!(Hasher, State, oneshot_fn, length_field)`.
+///
+///`length_field` is padding length encoding of 3 bytes message, placed at the end of block.
+macro_rules! test_compress_blocks {
+    ($hasher:ident, $state:ident, $oneshot:path, $length:expr) => {
+        #[test]
+        fn test_compress_blocks() {
+            const BLOCK: usize = $hasher::BLOCK_SIZE;
+
+            let mut blocks = [[0u8; BLOCK]; 4];
+            for (idx, block) in blocks.iter_mut().enumerate() {
+                for (byte_idx, byte) in block.iter_mut().enumerate() {
+                    *byte = (idx * BLOCK + byte_idx) as u8;
+                }
+            }
+            let mut data = Vec::new();
+            for block in blocks.iter() {
+                data.extend_from_slice(block);
+            }
+            data.extend_from_slice(b"tail");
+
+            let mut hasher = $hasher::new();
+            hasher.compress_blocks(&blocks[..1]);
+            hasher.compress_blocks(&[]);
+            hasher.compress_blocks(&blocks[1..]);
+            hasher.update(b"tail");
+            assert_eq!(hasher.result(), $oneshot(&data));
+
+            let mut hasher = $hasher::new();
+            hasher.update(&blocks[0]);
+            hasher.compress_blocks(&blocks[1..]);
+            hasher.update(b"tail");
+            assert_eq!(hasher.result(), $oneshot(&data));
+
+            //Unaligned input falls back to buffering
+            let mut unaligned = b"a".to_vec();
+            unaligned.extend_from_slice(&data);
+            let mut hasher = $hasher::new();
+            hasher.update(b"a");
+            hasher.compress_blocks(&blocks);
+            hasher.update(b"tail");
+            assert_eq!(hasher.result(), $oneshot(&unaligned));
+
+            //Raw compression with manual padding
+            let length = $length;
+            let mut block = [0u8; BLOCK];
+            block[..3].copy_from_slice(b"abc");
+            block[3] = 0x80;
+            block[BLOCK - length.len()..].copy_from_slice(&length);
+            let state = $state::new().compress(&block);
+            assert_eq!(state.to_bytes(), $oneshot(b"abc"));
+            assert_eq!($state::default(), $state::new());
+
+            let mut state = $state::new();
+            state.compress_blocks(&blocks);
+            let expected = blocks.iter().fold($state::new(), |state, block| state.compress(block));
+            assert_eq!(state, expected);
+            assert_ne!(state, $state::new());
+        }
+    };
+}
@@ -0,0 +1,12 @@
+//!Helpers shared by integration tests.
+//!
+//!Each test crate uses only part of them.
+#![allow(dead_code)]
+
+#[macro_use]
+mod hasher;
+
+///Decodes hex string of test vector.
+pub fn from_hex(input: &str) -> Vec<u8> {
+    (0..input.len()).step_by(2).map(|idx| u8::from_str_radix(&input[idx..idx + 2], 16).unwrap()).collect()
+}
@@ -0,0 +1,98 @@
+use lhash::crypt::{Algorithm, CryptHash, crypt, md5_crypt, parse, sha256_crypt, sha512_crypt, verify};
+
+#[test]
+fn test_sha256_crypt() {
+    //Test vectors from Drepper's specification
+    let tests: [(&'static str, &'static str, &'static str); 7] = [
+        ("Hello world!", "$5$saltstring", "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5"),
+        ("Hello world!", "$5$rounds=10000$saltstringsaltstring", "$5$rounds=10000$saltstringsaltst$3xv.VbSHBb41AL9AvLeujZkZRBAwqFMz2.opqey6IcA"),
+        ("This is just a test", "$5$rounds=5000$toolongsaltstring", "$5$rounds=5000$toolongsaltstrin$Un/5jzAHMgOGZ5.mWJpuVolil07guHPvOW8mGRcvxa5"),
+        ("a very much longer text to encrypt.  This one even stretches over morethan one line.", "$5$rounds=1400$anotherlongsaltstring", "$5$rounds=1400$anotherlongsalts$Rx.j8H.h8HjEDGomFU8bDkXm3XIUnzyxf12oP84Bnq1"),
+        ("we have a short salt string but not a short password", "$5$rounds=77777$short", "$5$rounds=77777$short$JiO1O3ZpDAxGJeaDIuqCoEFysAe1mZNJRs3pw0KQRd/"),
+        ("a short string", "$5$rounds=123456$asaltof16chars..", "$5$rounds=123456$asaltof16chars..$gP3VQ/6X7UUEW3HkBn2w1/Ptq2jxPyzV/cZKmF/wJvD"),
+        ("the minimum number is still observed", "$5$rounds=10$roundstoolow", "$5$rounds=1000$roundstoolow$yfvwcWrQ8l/K0DAWyuPMDNHpIVlTQebY9l/gL972bIC"),
+    ];
+
+    for (password, setting, expected) in tests.iter() {
+        let output = crypt(password.as_bytes(), setting).unwrap();
+        assert_eq!(output, *expected);
+        assert!(verify(password.as_bytes(), expected));
+        assert!(!verify(b"wrong", expected));
+    }
+
+    assert_eq!(sha256_crypt(b"Hello world!", "saltstring", None), "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5");
+    assert_eq!(sha256_crypt(b"Hello world!", "saltstringsaltstring", Some(10000)), "$5$rounds=10000$saltstringsaltst$3xv.VbSHBb41AL9AvLeujZkZRBAwqFMz2.opqey6IcA");
+}
+
+#[test]
+fn test_sha512_crypt() {
+    let tests: [(&'static str, &'static str, &'static str); 5] = [
+        ("Hello world!", "$6$saltstring", "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1"),
+        ("Hello world!", "$6$rounds=10000$saltstringsaltstring", "$6$rounds=10000$saltstringsaltst$OW1/O6BYHV6BcXZu8QVeXbDWra3Oeqh0sbHbbMCVNSnCM/UrjmM0Dp8vOuZeHBy/YTBmSK6H9qs/y3RnOaw5v."),
+        ("a short string", "$6$rounds=123456$asaltof16chars..", "$6$rounds=123456$asaltof16chars..$BtCwjqMJGx5hrJhZywWvt0RLE8uZ4oPwcelCjmw2kSYu.Ec6ycULevoBK25fs2xXgMNrCzIMVcgEJAstJeonj1"),
+        ("the minimum number is still observed", "$6$rounds=10$roundstoolow", "$6$rounds=1000$roundstoolow$kUMsbe306n21p9R.FRkW3IGn.S9NPN0x50YhH1xhLsPuWGsUSklZt58jaTfF4ZEQpyUNGc0dqbpBYYBaHHrsX."),
+        ("", "$6$", "$6$$/chiBau24cE26QQVW3IfIe68Xu5.JQ4E8Ie7lcRLwqxO5cxGuBhqF2HmTL.zWJ9zjChg3yJYFXeGBQ2y3Ba1d1"),
+    ];
+
+    for (password, setting, expected) in tests.iter() {
+        let output = crypt(password.as_bytes(), setting).unwrap();
+        assert_eq!(output, *expected);
+        assert!(verify(password.as_bytes(), expected));
+        assert!(!verify(b"wrong", expected));
+    }
+
+    assert_eq!(sha512_crypt(b"Hello world!", "saltstring", None), "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1");
+}
+
+#[test]
+fn test_md5_crypt() {
+    let tests: [(&'static str, &'static str, &'static str); 4] = [
+        ("Hello world!", "$1$saltstring", "$1$saltstri$YMyguxXMBpd2TEZ.vS/3q1"),
+        ("", "$1$", "$1$$qRPK7m23GJusamGpoGLby/"),
+        ("password", "$1$5pZSV9va", "$1$5pZSV9va$azfrPr6af3Fc7dLblQXVa0"),
+        ("0123456789abcdef0123456789abcdefX", "$1$$", "$1$$dmnAUtNkrKgPM5oa0OGHU1"),
+    ];
+
+    for (password, setting, expected) in tests.iter() {
+        let output = crypt(password.as_bytes(), setting).unwrap();
+        assert_eq!(output, *expected);
+        assert!(verify(password.as_bytes(), expected));
+        assert!(!verify(b"wrong", expected));
+    }
+
+    assert_eq!(md5_crypt(b"password", "5pZSV9va"), "$1$5pZSV9va$azfrPr6af3Fc7dLblQXVa0");
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(parse("$5$rounds=10000$saltstringsaltst$3xv.VbSHBb41AL9AvLeujZkZRBAwqFMz2.opqey6IcA"), Some(CryptHash {
+        algorithm: Algorithm::Sha256,
+        rounds: Some(10000),
+        salt: "saltstringsaltst",
+        hash: "3xv.VbSHBb41AL9AvLeujZkZRBAwqFMz2.opqey6IcA",
+    }));
+    assert_eq!(parse("$6$toolongsaltstringforsha"), Some(CryptHash {
+        algorithm: Algorithm::Sha512,
+        rounds: None,
+        salt: "toolongsaltstrin",
+        hash: "",
+    }));
+    assert_eq!(parse("$1$5pZSV9va$azfrPr6af3Fc7dLblQXVa0"), Some(CryptHash {
+        algorithm: Algorithm::Md5,
+        rounds: None,
+        salt: "5pZSV9va",
+        hash: "azfrPr6af3Fc7dLblQXVa0",
+    }));
+
+    assert_eq!(parse("$6$rounds=99999999999999999999$salt$hash").unwrap().rounds, Some(u32::MAX));
+    assert_eq!(parse("$6$rounds=4294967296$salt").unwrap().rounds, Some(u32::MAX));
+    assert_eq!(parse("$6$rounds=4294967295$salt").unwrap().rounds, Some(u32::MAX));
+
+    assert_eq!(parse("$2b$10$abcdefghijklmnopqrstuv"), None);
+    assert_eq!(parse("$5$rounds=abc$salt$hash"), None);
+    assert_eq!(parse("$5$rounds=$salt$hash"), None);
+    assert_eq!(parse("$5$rounds=-1$salt$hash"), None);
+    assert_eq!(parse("$5$salt$invalid:hash"), None);
+    assert!(!verify(b"", "$5$salt"));
+    assert!(!verify(b"", "plaintext"));
+}
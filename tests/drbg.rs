@@ -0,0 +1,395 @@
+use lhash::{DigestFmt, Digest, HmacDrbg, HashDrbg, DrbgError, Sha1, Sha256, Sha384, Sha512, Sha512_256};
+
+mod common;
+use common::from_hex;
+
+fn digest_to_hex(input: impl AsRef<[u8]>) -> String {
+    DigestFmt(input).to_string()
+}
+
+//Vectors follow CAVP layout: instantiate, optional reseed, two generate calls with returned bits taken from the last one.
+//Each vector names its source: CAVP response file with section and COUNT, or OpenSSL 3.5 `EVP_RAND` output for same inputs.
+struct Vector {
+    entropy: &'static str,
+    nonce: &'static str,
+    personalization: &'static str,
+    reseed: Option<(&'static str, &'static str)>,
+    additional: [&'static str; 2],
+    expected: &'static str,
+}
+
+fn check_hmac<D: Digest>(vectors: &[Vector]) {
+    for vector in vectors {
+        let mut drbg = HmacDrbg::<D>::new(&from_hex(vector.entropy), &from_hex(vector.nonce), &from_hex(vector.personalization));
+        if let Some((entropy, additional)) = vector.reseed {
+            drbg.reseed(&from_hex(entropy), &from_hex(additional));
+        }
+
+        let mut out = vec![0; vector.expected.len() / 2];
+        drbg.generate(&from_hex(vector.additional[0]), &mut out).unwrap();
+        drbg.generate(&from_hex(vector.additional[1]), &mut out).unwrap();
+        assert_eq!(drbg.reseed_counter(), 3);
+        assert_eq!(digest_to_hex(&out), vector.expected);
+    }
+}
+
+fn check_hash<D: Digest>(vectors: &[Vector]) {
+    for vector in vectors {
+        let mut drbg = HashDrbg::<D>::new(&from_hex(vector.entropy), &from_hex(vector.nonce), &from_hex(vector.personalization));
+        if let Some((entropy, additional)) = vector.reseed {
+            drbg.reseed(&from_hex(entropy), &from_hex(additional));
+        }
+
+        let mut out = vec![0; vector.expected.len() / 2];
+        drbg.generate(&from_hex(vector.additional[0]), &mut out).unwrap();
+        drbg.generate(&from_hex(vector.additional[1]), &mut out).unwrap();
+        assert_eq!(drbg.reseed_counter(), 3);
+        assert_eq!(digest_to_hex(&out), vector.expected);
+    }
+}
+
+#[test]
+fn test_hmac_drbg_sha1() {
+    let vectors = [
+        //CAVP drbgvectors_no_reseed/HMAC_DRBG.rsp [SHA-1] AdditionalInputLen = 0, COUNT = 0
+        Vector {
+            entropy: "e91b63309e93d1d08e30e8d556906875",
+            nonce: "f59747c468b0d0da",
+            personalization: "",
+            reseed: None,
+            additional: ["", ""],
+            expected: "b7928f9503a417110788f9d0c2585f8aee6fb73b220a626b3ab9825b7a9facc79723d7e1ba9255e40e65c249b6082a7bc5e3f129d3d8f69b04ed1183419d6c4f2a13b304d2c5743f41c8b0ee73225347",
+        },
+        //OpenSSL 3.5 HMAC-DRBG SHA1, personalization and additional input
+        Vector {
+            entropy: "63e5306832d1245decb579b7b4eb8354",
+            nonce: "e9ece91e01fdd990",
+            personalization: "61288ecc7101ae650e73798609ee6525",
+            reseed: None,
+            additional: ["9c684195eb541e9de0eec84b33658191", "bc7466b4d3ba3f82d6d511027212b01e"],
+            expected: "a0afe7fe74d558776b5b0cccbb47087310bab0f2183e4414fc71f1a57c3073119e57329317114a22567ec7d71cab9d9f4d8d66bcc040178d1c64d5ff4916acd9946f8c7f3d2076ba6bbb48c5d4dc36c5",
+        },
+        //OpenSSL 3.5 HMAC-DRBG SHA1, reseed with additional input
+        Vector {
+            entropy: "9a45876abd053b1cf8addf5c6b4816a5",
+            nonce: "e0e1ba4a94b49d23",
+            personalization: "b7818dc752e120f4a5225dd001a37cbb",
+            reseed: Some(("c8c4d953fc9263a43234c2e580442b81", "0a18063b5ab65e91dc49f8210ca0ac81")),
+            additional: ["a33785238a68c8304a6bfa139a06bd51", "67794ed1b09813f8d87b39b2f2c71c52"],
+            expected: "ed8d4d70c11714f70a0e13d3f28a37735427651b15fe58a6bc445e6e5e1186ab507fece681efa6297d55e22e52049fd67763c44dc637df66c9e4b0515e29eee0ad00d19071bf67fafa9defd518d675c2",
+        },
+    ];
+    check_hmac::<Sha1>(&vectors);
+}
+
+#[test]
+fn test_hmac_drbg_sha256() {
+    let vectors = [
+        //CAVP drbgvectors_no_reseed/HMAC_DRBG.rsp [SHA-256] AdditionalInputLen = 0, COUNT = 0
+        Vector {
+            entropy: "ca851911349384bffe89de1cbdc46e6831e44d34a4fb935ee285dd14b71a7488",
+            nonce: "659ba96c601dc69fc902940805ec0ca8",
+            personalization: "",
+            reseed: None,
+            additional: ["", ""],
+            expected: "e528e9abf2dece54d47c7e75e5fe302149f817ea9fb4bee6f4199697d04d5b89d54fbb978a15b5c443c9ec21036d2460b6f73ebad0dc2aba6e624abf07745bc107694bb7547bb0995f70de25d6b29e2d3011bb19d27676c07162c8b5ccde0668961df86803482cb37ed6d5c0bb8d50cf1f50d476aa0458bdaba806f48be9dcb8",
+        },
+        //CAVP drbgvectors_no_reseed/HMAC_DRBG.rsp [SHA-256] AdditionalInputLen = 256, COUNT = 0
+        Vector {
+            entropy: "d3cc4d1acf3dde0c4bd2290d262337042dc632948223d3a2eaab87da44295fbd",
+            nonce: "0109b0e729f457328aa18569a9224921",
+            personalization: "",
+            reseed: None,
+            additional: ["3c311848183c9a212a26f27f8c6647e40375e466a0857cc39c4e47575d53f1f6", "fcb9abd19ccfbccef88c9c39bfb3dd7b1c12266c9808992e305bc3cff566e4e4"],
+            expected: "9c7b758b212cd0fcecd5daa489821712e3cdea4467b560ef5ddc24ab47749a1f1ffdbbb118f4e62fcfca3371b8fbfc5b0646b83e06bfbbab5fac30ea09ea2bc76f1ea568c9be0444b2cc90517b20ca825f2d0eccd88e7175538b85d90ab390183ca6395535d34473af6b5a5b88f5a59ee7561573337ea819da0dcc3573a22974",
+        },
+        //CAVP drbgvectors_pr_false/HMAC_DRBG.rsp [SHA-256] AdditionalInputLen = 0, COUNT = 0
+        Vector {
+            entropy: "06032cd5eed33f39265f49ecb142c511da9aff2af71203bffaf34a9ca5bd9c0d",
+            nonce: "0e66f71edc43e42a45ad3c6fc6cdc4df",
+            personalization: "",
+            reseed: Some(("01920a4e669ed3a85ae8a33b35a74ad7fb2a6bb4cf395ce00334a9c9a5a5d552", "")),
+            additional: ["", ""],
+            expected: "76fc79fe9b50beccc991a11b5635783a83536add03c157fb30645e611c2898bb2b1bc215000209208cd506cb28da2a51bdb03826aaf2bd2335d576d519160842e7158ad0949d1a9ec3e66ea1b1a064b005de914eac2e9d4f2d72a8616a80225422918250ff66a41bd2f864a6a38cc5b6499dc43f7f2bd09e1e0f8f5885935124",
+        },
+        //CAVP drbgvectors_pr_false/HMAC_DRBG.rsp [SHA-256] AdditionalInputLen = 256, COUNT = 0
+        Vector {
+            entropy: "05ac9fc4c62a02e3f90840da5616218c6de5743d66b8e0fbf833759c5928b53d",
+            nonce: "2b89a17904922ed8f017a63044848545",
+            personalization: "",
+            reseed: Some(("2791126b8b52ee1fd9392a0a13e0083bed4186dc649b739607ac70ec8dcecf9b", "43bac13bae715092cf7eb280a2e10a962faf7233c41412f69bc74a35a584e54c")),
+            additional: ["3f2fed4b68d506ecefa21f3f5bb907beb0f17dbc30f6ffbba5e5861408c53a1e", "529030df50f410985fde068df82b935ec23d839cb4b269414c0ede6cffea5b68"],
+            expected: "02ddff5173da2fcffa10215b030d660d61179e61ecc22609b1151a75f1cbcbb4363c3a89299b4b63aca5e581e73c860491010aa35de3337cc6c09ebec8c91a6287586f3a74d9694b462d2720ea2e11bbd02af33adefb4a16e6b370fa0effd57d607547bdcfbb7831f54de7073ad2a7da987a0016a82fa958779a168674b56524",
+        },
+        //OpenSSL 3.5 HMAC-DRBG SHA256, personalization and additional input
+        Vector {
+            entropy: "394dc55da8c081bcbb0b55c768de2c30256b91cde032b913777ec1e185e3438f",
+            nonce: "f3b94a4e7347de870b212bb56d3a9b88",
+            personalization: "f3283df4e554b0123a40790d7cafa1022398ee61bb5b412abc479c7a1d9d18c4",
+            reseed: None,
+            additional: ["f7d794976bcd3ffacd70ef9536b8077baf4a3755b30f2c92c96a991b1f3db623", "5362f371eded89b2486ef494644e4448b5c75816b2b40dd125e2fc5ce264d6bc"],
+            expected: "246059a58c64225ec47c30230f21a0095dc7b69950c0b9162786693b7e18248a69a8117f79afd065c7165a817e8e3ea0a517b692c34e0b3ac12870199b6dd54fac691fda86b4d155685e5bb98e8f454fa7fa1d5f3d30840004e5e441ee06860615a2aa40ec2961e0a2b83997380cd75a42ff647c5b1469e87bed59a2f4518687",
+        },
+    ];
+    check_hmac::<Sha256>(&vectors);
+}
+
+#[test]
+fn test_hmac_drbg_sha512() {
+    let vectors = [
+        //OpenSSL 3.5 HMAC-DRBG SHA512, no reseed
+        Vector {
+            entropy: "9bf569eb4116ddaf3107de08dceb1e621c00a7a6f41ee698ec4e9a09b0924ebf",
+            nonce: "1ac8ea8c22a8867a9f40c09d3c7baf41",
+            personalization: "",
+            reseed: None,
+            additional: ["", ""],
+            expected: "dc0f5f339891313ee77740fdafc222eb7d32c43992e2b6df5a18123e6c94b0e91ac0470fe7417e83d5a430dbf56312bf1b506e34c85b0bd3a90598f222baad98b7f93bb311bbf0eb559497ecd54c5a11e3a86a8e1259b71ec296525f414df7b1de5463c7a7566734b446e58990303295411ebf189e69d43e0aafb5f64290ed69d0cc0be499a6675dd0d1f68797b93f3ea3dfbc35765797faf37c89a1648556968300b69cea10b9981fc762e20ffd17680782f37eaad1aa7530b3450cfa7eb61e1f4d0c094194d304c8e9b5f3c95f824246dc825381cead74f24feb2afee848fb6f4c0702585fbcfa9eabb76c451a3e54d4090138bcb9de83e33acb60a1fbed5b",
+        },
+        //OpenSSL 3.5 HMAC-DRBG SHA512, personalization and additional input
+        Vector {
+            entropy: "b3f0fee0bd10ab1460a5c4be119b11b82f1db620481f1cea7f6d4aefd8a6858a",
+            nonce: "745987098aae24b66e1b7a4ad9a44d39",
+            personalization: "a7ff54729e38c4ea7ef2b3b70b0afda782c38c924f9b5b675a7754c414fb5933",
+            reseed: None,
+            additional: ["3b6df2ca6446b30c6af9a0eec2bb1a32547334278eff34c1bf5c761e382419b7", "d5b85288dbfb7a84e0a339b281a6a6fa482f75319213b91aa6f78c2df9bb577e"],
+            expected: "2ab2ff52cfcf27509edbf5eb2e7e30fb38cd738d4d156389c7c1bd2b41885b3a4c9960827e0651cddbc772054f767c115022b3d757d0c8b9b63f0d66309e670a4ffe1c6a73917392f952c35e86e9d394cf330ce41f48865149f595976bd32857ace36b7cdda00b4d160bcefbec87c5e825c0a68d295747c9a6623b49f4196313407455df30043d4597393593a7cd3761817c1f4ea2df6da89bccbf9f5b73b7008db908051449e2bd9e7e5223d9cc41fa68029e21f38747e257b443d145b684db8375ddea048db683d82031f288119c49a6fd43704101d4a9cfaf5e9897d50e524cd386abca3f9e0b641ba0705a7335334ec946add0c704fa5f5c0bbc7c20e67e",
+        },
+        //OpenSSL 3.5 HMAC-DRBG SHA512, reseed with additional input
+        Vector {
+            entropy: "cf85203a1870980c5645ee9beaecf9d7371edae849c055058a47962ecf0b7f7c",
+            nonce: "4951f841a901b1e5cfb486e2faeb7dc5",
+            personalization: "031dd521ae2ee0202e348fb5e45fa9eb9124d4e4bb77e3b85367a453df4e905e",
+            reseed: Some(("d86cf2673fdc5bb84d5de2cb34208ac8d7818f3f2b5676f6948ec7084b49cae6", "5f4427cf8bbc9a8970493e6d5a5293c1257a08488fab327e429a3e81449ab818")),
+            additional: ["0f8110c46c3bff3379bceb92b7f535aef9e5f006fa36a025bbdea89c3b715398", "bb5529702811236c7187cb4492758a7768973c4f7cfba5f9d44781424e21be5e"],
+            expected: "d6bcf63cd46e4506cc8a8e58c840c90077c0da4bddb3068abe4126cc6b591b4c3808bca85cee062f8965bd65b8771765ab6b611edbad29cd84f01e6588d4bf3803d05a87943acb67f612a2bd9ea8218de61fa6a654f376838c421e29f17e89b888b4f6f686cca691c5903bc8b47b416287748a0942b24b24ac5a5a05ab2352720479fd6a102d319a2e280073216176795c9ced97fa4c344961eca1677d3c532949e15fad319c5705d6c158fc80593f8b6854cae8b82662eac40328d7eba64839f57112f64e45bfe7d152d1fc310acadfeec24aa6126f75f5dfd25c0bc4142e37cf3c06448b57100c4c1913c036125f31c740adfae71491fb48c91ccb6c9c6f9c",
+        },
+    ];
+    check_hmac::<Sha512>(&vectors);
+}
+
+#[test]
+fn test_hash_drbg_sha1() {
+    let vectors = [
+        //CAVP drbgvectors_pr_false/Hash_DRBG.rsp [SHA-1] AdditionalInputLen = 0, COUNT = 0
+        Vector {
+            entropy: "1610b828ccd27de08ceea032a20e9208",
+            nonce: "492cf1709242f6b5",
+            personalization: "",
+            reseed: Some(("72d28c908edaf9a4d1e526d8f2ded544", "")),
+            additional: ["", ""],
+            expected: "56f33d4fdbb9a5b64d26234497e9dcb87798c68d08f7c41199d4bddf97ebbf6cb5550e5d149ff4d5bd0f05f25a6988c17436396227184af84a564335658e2f8572bea333eee2abff22ffa6de3e22aca2",
+        },
+        //OpenSSL 3.5 HASH-DRBG SHA1, personalization and additional input
+        Vector {
+            entropy: "63e5306832d1245decb579b7b4eb8354",
+            nonce: "e9ece91e01fdd990",
+            personalization: "61288ecc7101ae650e73798609ee6525",
+            reseed: None,
+            additional: ["9c684195eb541e9de0eec84b33658191", "bc7466b4d3ba3f82d6d511027212b01e"],
+            expected: "f5c1d49e193c13e3fac26242270a18c27413c38298c85b4ea25c4602a00a1b963279870bc22fdd9c418dfa83df1426d07c58c490ce019446806f0b04c780b8b2125e913438cc74b3be145d35c539fcc1",
+        },
+        //OpenSSL 3.5 HASH-DRBG SHA1, reseed with additional input
+        Vector {
+            entropy: "9a45876abd053b1cf8addf5c6b4816a5",
+            nonce: "e0e1ba4a94b49d23",
+            personalization: "b7818dc752e120f4a5225dd001a37cbb",
+            reseed: Some(("c8c4d953fc9263a43234c2e580442b81", "0a18063b5ab65e91dc49f8210ca0ac81")),
+            additional: ["a33785238a68c8304a6bfa139a06bd51", "67794ed1b09813f8d87b39b2f2c71c52"],
+            expected: "ee8012d81005ca594e1e12cc5808f5ea6cc706da09639d01f9af5e5a10e488555541e8bc027f1586b95d64fff601ae3ff606b52e521e1ee42d40ba6214c3b9041dfee883834ad15628ec92dac54f2cf1",
+        },
+    ];
+    check_hash::<Sha1>(&vectors);
+}
+
+#[test]
+fn test_hash_drbg_sha256() {
+    let vectors = [
+        //CAVP drbgvectors_no_reseed/Hash_DRBG.rsp [SHA-256] AdditionalInputLen = 0, COUNT = 0
+        Vector {
+            entropy: "a65ad0f345db4e0effe875c3a2e71f42c7129d620ff5c119a9ef55f05185e0fb",
+            nonce: "8581f9317517276e06e9607ddbcbcc2e",
+            personalization: "",
+            reseed: None,
+            additional: ["", ""],
+            expected: "d3e160c35b99f340b2628264d1751060e0045da383ff57a57d73a673d2b8d80daaf6a6c35a91bb4579d73fd0c8fed111b0391306828adfed528f018121b3febdc343e797b87dbb63db1333ded9d1ece177cfa6b71fe8ab1da46624ed6415e51ccde2c7ca86e283990eeaeb91120415528b2295910281b02dd431f4c9f70427df",
+        },
+        //OpenSSL 3.5 HASH-DRBG SHA256, personalization and additional input
+        Vector {
+            entropy: "394dc55da8c081bcbb0b55c768de2c30256b91cde032b913777ec1e185e3438f",
+            nonce: "f3b94a4e7347de870b212bb56d3a9b88",
+            personalization: "f3283df4e554b0123a40790d7cafa1022398ee61bb5b412abc479c7a1d9d18c4",
+            reseed: None,
+            additional: ["f7d794976bcd3ffacd70ef9536b8077baf4a3755b30f2c92c96a991b1f3db623", "5362f371eded89b2486ef494644e4448b5c75816b2b40dd125e2fc5ce264d6bc"],
+            expected: "3faec8f4b9aa156c4d4fdee3361c8af4e194e68d3ed7864b060472d36703c251b07dabd8e0905f4559a603cfcd4dfd2db1f5eb0bcc6fb95d33ee13cf486afcdb928690bbb05c15d4887ae3e64adf28263f3fd5fd0a78e5ccb87f11fe44ab0e89c81bb2292c29f7b1c487a84b4901e174e76be3a2158ead616cb1ee52c6c53fe4",
+        },
+        //OpenSSL 3.5 HASH-DRBG SHA256, reseed with additional input
+        Vector {
+            entropy: "56640f4390a0075ae0323d1ad3044432c1d1dc172b5ee73b7dc266e5b29d2969",
+            nonce: "6f011db3a9613e4bace5aa7eac34088d",
+            personalization: "4fc9fe024472019f9dd18b2682f3b103e5009a5a1fc0af4feac555a7eb44f5f7",
+            reseed: Some(("fabc0f5a150ca0048beada300ee7c9a621f3829af00414573704e6f895715e76", "ec88cf89501dabc966cc85e9ca390b7a83d798ecffd796e03c2d997b072b4e2e")),
+            additional: ["fd902e3f7557516499b3208646631d5e83d88cf4d32fe61921e5f68b9b9363b1", "55a5eea894aac8edc37683bf28878bfe47b229b8a71989fd101fd4402be88ec5"],
+            expected: "0b69c4bbaacc2a2286cdf81f3daa9a7724223bd58866476fa0e5d897c2839455f9e52c9e872128280e37ead180ac2ef9552cd5b3f64ec150fedbb8bdd3e626361640a21d1debc924d624d2d410b147e2def5060c67ad6543b85f09b75fd68b81ed77991b01a8aab5e4cb3925604452d6ba795c466fc4b9adac59607eef082c3e",
+        },
+    ];
+    check_hash::<Sha256>(&vectors);
+}
+
+#[test]
+fn test_hash_drbg_sha512() {
+    let vectors = [
+        //OpenSSL 3.5 HASH-DRBG SHA512, no reseed
+        Vector {
+            entropy: "9bf569eb4116ddaf3107de08dceb1e621c00a7a6f41ee698ec4e9a09b0924ebf",
+            nonce: "1ac8ea8c22a8867a9f40c09d3c7baf41",
+            personalization: "",
+            reseed: None,
+            additional: ["", ""],
+            expected: "ccd228db38dd7c3a2bc798bc8fae2b5b5d6de9afb5d71211a15f73b7d05dc161178c47c296c11efa9922f264dcdeaf5a58b52dc8cefe99816a2f99a5c3a73fa5735b74ed90260368bc0898b240ac141ae221c7a93ed3b56db91e8100070435c3c2d440321c79810356a5984c2e9f5b831a364c8c31da315489b83a63d1de4ce75050c3a8f36500352163456d1418918f87ba6ee8aaf3986734a37d14f0587d36fde0a1ea0cd413d33999dca6e7947f63968e49822f9c85986e4636767bb53aee69c657e76596637533b86ceff2cee5b666ff36a684d1976937fe4c13804acdade6cb885ca9abeb7c445ed4c13cd8b8be7ae4259f3c5fbeaa41438a925db3c3e9",
+        },
+        //OpenSSL 3.5 HASH-DRBG SHA512, personalization and additional input
+        Vector {
+            entropy: "b3f0fee0bd10ab1460a5c4be119b11b82f1db620481f1cea7f6d4aefd8a6858a",
+            nonce: "745987098aae24b66e1b7a4ad9a44d39",
+            personalization: "a7ff54729e38c4ea7ef2b3b70b0afda782c38c924f9b5b675a7754c414fb5933",
+            reseed: None,
+            additional: ["3b6df2ca6446b30c6af9a0eec2bb1a32547334278eff34c1bf5c761e382419b7", "d5b85288dbfb7a84e0a339b281a6a6fa482f75319213b91aa6f78c2df9bb577e"],
+            expected: "b53b9d28d2803e4548f1d43678bf070b202ee15506cc1415f6fc9f110c0c10ab4cde30fbfceb2471a65f7001985a0b48aec2b837318c21416e3c7d1081b791ed1563a21f2d3cc76173685b6129fdc3c33b0c2bfefc1f13b718f07c853f8bd1ecce3c3ffcd2e13722a37f54dcf6795aadc1ff846b6769e65c0b82ed2175386bd8cb5ce7dd3dfa7bbe9a1aaaa31a5c4a17fd4308ffd5730f8b7c45eaf7059319c5f70ef7c4fd4345b3376893eda78362cf9575567a2846544082b8f7ae18a6ad72b6882f6395d76af42543e78425c50fc9692d66c6c15b3a3b332f84cecf362605da8b2bd4cb8f02b13cde9ae36b46decb2c976b662c926a7d83c0cdf6baf47227",
+        },
+        //OpenSSL 3.5 HASH-DRBG SHA512, reseed with additional input
+        Vector {
+            entropy: "cf85203a1870980c5645ee9beaecf9d7371edae849c055058a47962ecf0b7f7c",
+            nonce: "4951f841a901b1e5cfb486e2faeb7dc5",
+            personalization: "031dd521ae2ee0202e348fb5e45fa9eb9124d4e4bb77e3b85367a453df4e905e",
+            reseed: Some(("d86cf2673fdc5bb84d5de2cb34208ac8d7818f3f2b5676f6948ec7084b49cae6", "5f4427cf8bbc9a8970493e6d5a5293c1257a08488fab327e429a3e81449ab818")),
+            additional: ["0f8110c46c3bff3379bceb92b7f535aef9e5f006fa36a025bbdea89c3b715398", "bb5529702811236c7187cb4492758a7768973c4f7cfba5f9d44781424e21be5e"],
+            expected: "16c47ed48c1ec2aff9b73e1b59270491db17e40f64f62884f4a4c5f3fb0497a5a00f1aba311b59681f64dc376e37e15440cced3d36674fea2980134f7befa9f42dc069c88ee2108b88bfa7b20748a322a3c017998aa30877f664050e78cb4e8760db1614bd7d55eba57cb4a61555cf8ba1911e10d8e2d18af36aa8bbe4a9a639589485ae1535bb7961d1d8d8f382eea8b32892bb193e281c7647084d42205c1e2b5ab8b4b95de8adfe3e91062aa87db76e9ac58e982feaa6c4730911f8050ba52a9cd7a7bf6b47ba0883408080e1b656ecc337b148c66770d3b94ee5d70ebdf7f755a0b4a2cb7194e5f8014309740fba23b9d5b769b569bc8d55a6d3339f3bd7",
+        },
+    ];
+    check_hash::<Sha512>(&vectors);
+}
+
+#[test]
+fn test_hmac_drbg_sha384() {
+    let vectors = [
+        //OpenSSL 3.5 HMAC-DRBG SHA384, no reseed
+        Vector {
+            entropy: "3fab830673c13b242ef275b59853359174c6b0398c7e06f87dbf84db2d98dab0",
+            nonce: "a8e95ce950aa0e8d509a69f72c7f66c3",
+            personalization: "",
+            reseed: None,
+            additional: ["", ""],
+            expected: "950f61483f0111dfe4ac0d09d663edd80883e15751ad99d38a03736fd15a13b7216d9fc2ee123c513344b2fb52b95e99b3246d5cce3fb04c2d0cb6fc2e724fab22086d6c86c3e0a43141919a2af18b006149fe9c11aba58cf34f24d6382134968f910ba8f0b807493b9ce2bcf9e873c85361b8a998e9fef0d2b30acfea85134444da0c2218e7a1799a59ca0fc64e2f4a1fcbeae798f5bf9edb2a5c9023678c21638054b56a6a9b5e29775dde40c1b794beb8b503f6e71b7767717c177f429f22",
+        },
+        //OpenSSL 3.5 HMAC-DRBG SHA384, reseed with additional input
+        Vector {
+            entropy: "3fab830673c13b242ef275b59853359174c6b0398c7e06f87dbf84db2d98dab0",
+            nonce: "a8e95ce950aa0e8d509a69f72c7f66c3",
+            personalization: "374232d97e527a47b5830f54157a6857cbfa3a24224f6b57453a1e1130db5978",
+            reseed: Some(("645f3b240f3c0bae26734dbf0e3c0ec904070cd9cb9132005a3ac08b26f44922", "c33396d5eea19b98f82da1fee3eeb41e289c2192035431bab73756806a8508dd")),
+            additional: ["2fbfc5bc42f2ffe23abae2f838d2f7635b2ff5620cbae346d66e3eef0bd91900", "b3c811f8c6529936fd050069dd78622c75db7f012b11684c6227f9c29a91e26a"],
+            expected: "7a84d9df39a84158fe4f3e53851e8af4db9835e5448a138fb619a8bb281d2254422596fae72d6a11ecab6125eff47206faeb35ffd036fe2a1f617b5b7d0257526db18c10c5c757d290438c50cc23f97c163626ab1bceda77f4020bd2f4dd254d40bb4416b069fd7eaf82d977062d91ba936f289b9d949c79845c310202fc5550956c8ffa0d51d4fb2f4b842f83639cc1f651bee2ea54212681ad7b23296a2dc8a74ffa7d6e239eb4e11dc2bc594ec71cd287ac12ef712414b909e3948605aec2",
+        },
+    ];
+    check_hmac::<Sha384>(&vectors);
+}
+
+#[test]
+fn test_hmac_drbg_sha512_256() {
+    let vectors = [
+        //OpenSSL 3.5 HMAC-DRBG SHA2-512/256, no reseed
+        Vector {
+            entropy: "3fa4b057b4cfa69f5d6c29cf6f883c0c69f7f2bebab4135b8e01e1ce5b6ab7e1",
+            nonce: "1f9bcde8258e741f66b3a0ef17006eee",
+            personalization: "",
+            reseed: None,
+            additional: ["", ""],
+            expected: "00396594e623d7afcfc4841a81f7cef149355ec3e7c72c7a685d5eb6c719b7fc4d70e81d30bb31cd51c172cca5e633358934a9fd972242773c4e5ba6e9f412738fe77c1a79a9709793853daad67ac7b38091f520a242bd9f2f95f64100917e9cf97d4640667000fc7b3745bf81471899a6e4856a82b0e5658db1528802715193",
+        },
+        //OpenSSL 3.5 HMAC-DRBG SHA2-512/256, reseed with additional input
+        Vector {
+            entropy: "3fa4b057b4cfa69f5d6c29cf6f883c0c69f7f2bebab4135b8e01e1ce5b6ab7e1",
+            nonce: "1f9bcde8258e741f66b3a0ef17006eee",
+            personalization: "1833c5434aaff5d7df034f1d01d4f3aa86c392f0513c7f9f2e554d6b1d782a99",
+            reseed: Some(("c24b2b9e5407e48d158da6090d08968a308e128648093003890389c7f9cea88a", "8e5d356fcc7c95f354e432853092c3a9ad0b21616211a2957be988946bbc9a69")),
+            additional: ["0284b4106e07cddd6e1ee8da892877ab8555eb33bb8181e8ee31e15c0892b663", "7f1139e30b8ff0d68350cb7911fa548e6fdf6cfbb771f5fdb7f9167c4f0fa841"],
+            expected: "f4b4be3a367ff987a460345750900eae13c2d831396a79c7eb0a32d7c8334cf1afbbac6b6a29cd3a7191e75a5f5f1f17cf98423b658e46a78f60b421c71b8f39e18195a93e9a18688c3b901e999d9347a483e05205f53506871d254e1c7eb997b2e8a15ddd8ab3c683c5c65cdc88c5d274e16219e39e619d7d4954e51a53de07",
+        },
+    ];
+    check_hmac::<Sha512_256>(&vectors);
+}
+
+#[test]
+fn test_hash_drbg_sha384() {
+    let vectors = [
+        //OpenSSL 3.5 HASH-DRBG SHA384, no reseed
+        Vector {
+            entropy: "960125f12a5edbb248f16afbdaf7765c0197859535c1ac68a0f83f27ba39cc47",
+            nonce: "77b498c04d521842ba2e937cdf0a812a",
+            personalization: "",
+            reseed: None,
+            additional: ["", ""],
+            expected: "19eef988764c08a636c8ef4300518ae85d796474886e2523d2f57d9eef0c9d2681fa2317991110f7a2fbccac38fea79488a7c31293a8ee0d55ef11045b8940298a5f703530a4f0664faf7467dfc9c00d8637420d8bc98e3da8b52e2c08f1d7542a6b86e45b894b3498ae52c752601789135f19824d40f4ef14579ca6556b4965de262545d6df0f2e4b42aadcb20b3e8b8ea597e68edddcca1fd77ce3644016727fe9b58847025472cdd9763e019386a060186d05b79407f7504999154d123457",
+        },
+        //OpenSSL 3.5 HASH-DRBG SHA384, reseed with additional input
+        Vector {
+            entropy: "960125f12a5edbb248f16afbdaf7765c0197859535c1ac68a0f83f27ba39cc47",
+            nonce: "77b498c04d521842ba2e937cdf0a812a",
+            personalization: "54a3edb88c45f5c883697df1b77cdb031230ce35d3cd1e9033dde88163f43424",
+            reseed: Some(("9cd48de66cffb72dc61267d1b4b866ac6388ebdf534a208bfafcfe5886386871", "ad28f7cd6b0bfaaf2e74bb3be281ecebbb24560fcb6c885b7f2e7702fc704f55")),
+            additional: ["bbd3f9c5590a3a41219c9dce16cf7d7442bb25e36b136fa0f17c33a75bb03c00", "240e62300b777883a3bfa775c6c9abfdeed8fbad41e5cb54e0582d205c319e35"],
+            expected: "e4ead427a56b2a26dc89121a77ffd345ca5fc089b48a7983b09466d147b0fc0a8ced51824364e94b83705d838c918ffed1fc58c34f365be6cbeaec555fb2f755ebde1a10bfd103ce2635febb46a6dd66dbed94fba3cc396a07a59ff391a7515106a4defb321031b4acb97962f081df5a2ea0d45fa35abf197ddf64bc09d6cc8138793805f6183afa05809213542056abe4a4b049010fa61f3ac8c890d3f1ee5ac06e4900ce702fe9172077982345eaba2f0ca9379e2d193cf2fa861357533441",
+        },
+    ];
+    check_hash::<Sha384>(&vectors);
+}
+
+#[test]
+fn test_hash_drbg_sha512_256() {
+    let vectors = [
+        //OpenSSL 3.5 HASH-DRBG SHA2-512/256, no reseed
+        Vector {
+            entropy: "bdb2a67470893fc843ad1885e776a158b6acd1030abcfbd304fc60e2af5c5e63",
+            nonce: "50db2dee16e84f7495d29a72a311d00c",
+            personalization: "",
+            reseed: None,
+            additional: ["", ""],
+            expected: "f31a43218cc4d8655615009bb0bff519a09e1947062cba9dcea670a27f1ceb865967b0e931006710a31d37282b11a8fab8b6c1536346642df44ff09d8392d873e202e9230def2c506e717138a8d3e6351185653a9293e469af6cb94d07cb92b38eb9fa48ca6318f6147de220f7db2d1e14538882cce0aaca2b3e9a7a0244a54d",
+        },
+        //OpenSSL 3.5 HASH-DRBG SHA2-512/256, reseed with additional input
+        Vector {
+            entropy: "bdb2a67470893fc843ad1885e776a158b6acd1030abcfbd304fc60e2af5c5e63",
+            nonce: "50db2dee16e84f7495d29a72a311d00c",
+            personalization: "1fca0c30266d5eac89b2203b6dc0bae55d270092c5ac218a4fd5f2b0a80ae3bb",
+            reseed: Some(("68adf2b018eb160a44e69483f14d0f4af3a3f99e6a312c94d126a267ab2b58c4", "8abaca3a43bb4679960601a839b853dbd3554f98b00a83a767ce39ad71107f6f")),
+            additional: ["eddb07efad2f0563dc3a2fd4b7fdd04f1e2d82965bcd29b5307c4922d7ca0079", "ce2e4c7025d48a7ce908c17f5d3dd8ee8687c6118e24a7d81dd32072fa9a70a3"],
+            expected: "c7faf1b121a61480de79d8d12d09517d6605a6b1ce2ccd9e8a4c25a433b6a4beb67c551e00e84de1d96af14a0b3198aee234b4d3f198bae4d083e5448378327729ebf1556de11fcbe360c7c661add38baad8ee8cbf0bf0ee408238d9a0f206729b92b6fb00b3ca27d257432bb57a45fe951f0fc4dc76102ef0e49127d1860765",
+        },
+    ];
+    check_hash::<Sha512_256>(&vectors);
+}
+
+#[test]
+fn test_reseed_required() {
+    let mut out = [0u8; 32];
+
+    let mut drbg = HmacDrbg::<Sha256>::new(&[0x55; 32], &[0xAA; 16], b"");
+    drbg.set_reseed_interval(2);
+    assert_eq!(drbg.generate(b"", &mut out), Ok(()));
+    assert_eq!(drbg.generate(b"", &mut out), Ok(()));
+    assert_eq!(drbg.generate(b"", &mut out), Err(DrbgError::ReseedRequired));
+    drbg.reseed(&[0x11; 32], b"");
+    assert_eq!(drbg.reseed_counter(), 1);
+    assert_eq!(drbg.generate(b"", &mut out), Ok(()));
+
+    let mut drbg = HashDrbg::<Sha256>::new(&[0x55; 32], &[0xAA; 16], b"");
+    drbg.set_reseed_interval(1);
+    assert_eq!(drbg.generate(b"", &mut out), Ok(()));
+    assert_eq!(drbg.generate(b"", &mut out), Err(DrbgError::ReseedRequired));
+    drbg.reseed(&[0x11; 32], b"");
+    assert_eq!(drbg.generate(b"", &mut out), Ok(()));
+
+    let mut out = vec![0u8; (1 << 16) + 1];
+    assert_eq!(drbg.generate(b"", &mut out), Err(DrbgError::RequestTooLarge));
+}
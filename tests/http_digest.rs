@@ -0,0 +1,229 @@
+use lhash::{Md5, Sha256, Sha512};
+use lhash::http_digest::{parse_authorization, parse_challenge, Algorithm, Qop, Request};
+use lhash::http_digest;
+
+const RFC7616_NONCE: &str = "7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v";
+const RFC7616_CNONCE: &str = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+const RFC7616_OPAQUE: &str = "FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS";
+
+fn rfc7616_request(qop: Option<Qop>) -> Request<'static> {
+    Request {
+        username: "Mufasa",
+        password: b"Circle of Life",
+        method: "GET",
+        uri: "/dir/index.html",
+        qop,
+        body: b"",
+        cnonce: RFC7616_CNONCE,
+        nc: 1,
+    }
+}
+
+#[test]
+fn test_parse_challenge() {
+    let challenge = parse_challenge(r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#).unwrap();
+    assert_eq!(challenge.realm, "http-auth@example.org");
+    assert_eq!(challenge.nonce, RFC7616_NONCE);
+    assert_eq!(challenge.opaque, Some(RFC7616_OPAQUE));
+    assert_eq!(challenge.algorithm, Algorithm::Sha256);
+    assert!(!challenge.session);
+    assert!(challenge.qop_auth);
+    assert!(challenge.qop_auth_int);
+    assert!(!challenge.stale);
+    assert!(!challenge.userhash);
+
+    let challenge = parse_challenge(r#"digest realm="my \"realm\"",nonce=abc,algorithm=sha-512-256-SESS,stale=TRUE,userhash=true,domain="/a /b""#).unwrap();
+    assert_eq!(challenge.realm, r#"my \"realm\""#);
+    assert_eq!(challenge.nonce, "abc");
+    assert_eq!(challenge.domain, Some("/a /b"));
+    assert_eq!(challenge.algorithm, Algorithm::Sha512_256);
+    assert!(challenge.session);
+    assert!(challenge.stale);
+    assert!(challenge.userhash);
+    assert!(!challenge.qop_auth);
+
+    let challenge = parse_challenge(r#"Digest realm="testrealm@host.com", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093""#).unwrap();
+    assert_eq!(challenge.algorithm, Algorithm::Md5);
+
+    assert!(parse_challenge(r#"Basic realm="test""#).is_none());
+    assert!(parse_challenge(r#"Digestrealm="test", nonce="abc""#).is_none());
+    assert!(parse_challenge(r#"Digest nonce="abc""#).is_none());
+    assert!(parse_challenge(r#"Digest realm="test", nonce="abc", algorithm=SHA-1"#).is_none());
+    assert!(parse_challenge(r#"Digest realm="test, nonce=abc"#).is_none());
+}
+
+#[test]
+fn test_response() {
+    //RFC 7616 section 3.9.1
+    let ha1 = http_digest::ha1::<Md5>("Mufasa", "http-auth@example.org", b"Circle of Life");
+    let ha2 = http_digest::ha2::<Md5>("GET", "/dir/index.html", None);
+    let result = http_digest::response::<Md5>(ha1.as_str(), RFC7616_NONCE, Some(Qop::Auth), 1, RFC7616_CNONCE, ha2.as_str());
+    assert_eq!(result, "8ca523f5e9506fed4657c9700eebdbec");
+
+    let ha1 = http_digest::ha1::<Sha256>("Mufasa", "http-auth@example.org", b"Circle of Life");
+    let ha2 = http_digest::ha2::<Sha256>("GET", "/dir/index.html", None);
+    let result = http_digest::response::<Sha256>(ha1.as_str(), RFC7616_NONCE, Some(Qop::Auth), 1, RFC7616_CNONCE, ha2.as_str());
+    assert_eq!(result, "753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1");
+
+    //RFC 2617 section 3.5
+    let ha1 = http_digest::ha1::<Md5>("Mufasa", "testrealm@host.com", b"Circle Of Life");
+    let ha2 = http_digest::ha2::<Md5>("GET", "/dir/index.html", None);
+    let result = http_digest::response::<Md5>(ha1.as_str(), "dcd98b7102dd2f0e8b11d0f600bfb0c093", Some(Qop::Auth), 1, "0a4f113b", ha2.as_str());
+    assert_eq!(result, "6629fae49393a05397450978507c4ef1");
+    let result = http_digest::response::<Md5>(ha1.as_str(), "dcd98b7102dd2f0e8b11d0f600bfb0c093", None, 0, "", ha2.as_str());
+    assert_eq!(result, "670fd8c2df070c60b045671b8b24ff02");
+}
+
+#[test]
+fn test_response_sha512() {
+    //Generic functions accept digests up to 64 bytes.
+    let ha1 = http_digest::ha1::<Sha512>("Mufasa", "http-auth@example.org", b"Circle of Life");
+    assert_eq!(ha1, "59c51e6435781e850d72bc9c7bae246e4f958041071fd81ca4cba98f47a0b9ace91378c6336a0e87c25af6f8c7a5f684e4a6e29d0846daabe87303fdc772a9a9");
+    let ha2 = http_digest::ha2::<Sha512>("GET", "/dir/index.html", None);
+    assert_eq!(ha2, "9896310cdd77fbbe161f12b6075c8963e4798787f138a8f152be75d04f61cb98b28ecb67c99ac42a80dd0bfb768b6d432b08cfd6e7467b4fd296b7152bc8e232");
+    let result = http_digest::response::<Sha512>(ha1.as_str(), RFC7616_NONCE, Some(Qop::Auth), 1, RFC7616_CNONCE, ha2.as_str());
+    assert_eq!(result, "27d9ebedb4e86595d8b99152ce456620c8e47c48afbd771dbc4468bb758ca66e7312383d49ad044219d4a2c5c218e66c584fa00b2728798bcff9435825eed0ad");
+    let result = http_digest::userhash::<Sha512>("Mufasa", "http-auth@example.org");
+    assert_eq!(result, "10de1a728495dac85f6edefb963042c3aa9845a2ffd3985014a6c0d655b2de7a8137db4cece57e6906f14b6fd258ee5964927c74a735ac9d57718a79961b4181");
+    let result = http_digest::ha1_sess::<Sha512>(ha1.as_str(), RFC7616_NONCE, RFC7616_CNONCE);
+    assert_eq!(result.as_str().len(), 128);
+}
+
+#[test]
+fn test_authorization() {
+    let mut out = [0u8; 512];
+
+    let challenge = parse_challenge(r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#).unwrap();
+    let header = challenge.authorization(&rfc7616_request(Some(Qop::Auth)), &mut out).unwrap();
+    assert_eq!(header, r#"Digest username="Mufasa", realm="http-auth@example.org", uri="/dir/index.html", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", nc=00000001, cnonce="f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ", qop=auth, response="753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#);
+
+    let parsed = parse_authorization(header).unwrap();
+    assert_eq!(parsed.username, "Mufasa");
+    assert_eq!(parsed.uri, "/dir/index.html");
+    assert_eq!(parsed.nc, 1);
+    assert_eq!(parsed.qop, Some(Qop::Auth));
+    assert!(parsed.verify("Mufasa", b"Circle of Life", "GET", b""));
+    assert!(!parsed.verify("Mufasa", b"Circle of life", "GET", b""));
+    assert!(!parsed.verify("Mufasa", b"Circle of Life", "POST", b""));
+    assert!(!parsed.verify("Simba", b"Circle of Life", "GET", b""));
+
+    assert!(challenge.authorization(&rfc7616_request(Some(Qop::Auth)), &mut out[..64]).is_none());
+    assert!(challenge.authorization(&rfc7616_request(None), &mut out).is_none());
+
+    let challenge = parse_challenge(r#"Digest realm="testrealm@host.com", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093""#).unwrap();
+    assert!(challenge.authorization(&rfc7616_request(Some(Qop::Auth)), &mut out).is_none());
+    let request = Request {
+        password: b"Circle Of Life",
+        ..rfc7616_request(None)
+    };
+    let header = challenge.authorization(&request, &mut out).unwrap();
+    assert_eq!(header, r#"Digest username="Mufasa", realm="testrealm@host.com", uri="/dir/index.html", algorithm=MD5, nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", response="670fd8c2df070c60b045671b8b24ff02""#);
+    assert!(parse_authorization(header).unwrap().verify("Mufasa", b"Circle Of Life", "GET", b""));
+}
+
+#[test]
+fn test_authorization_sess_int() {
+    let mut out = [0u8; 512];
+
+    let challenge = parse_challenge(r#"Digest realm="http-auth@example.org", qop="auth-int", algorithm=SHA-256-sess, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v""#).unwrap();
+    let request = Request {
+        method: "POST",
+        body: b"hello",
+        nc: 10,
+        ..rfc7616_request(Some(Qop::AuthInt))
+    };
+    assert!(challenge.authorization(&rfc7616_request(Some(Qop::Auth)), &mut out).is_none());
+    let header = challenge.authorization(&request, &mut out).unwrap();
+    assert!(header.contains("algorithm=SHA-256-sess"));
+    assert!(header.contains("nc=0000000a"));
+    assert!(header.contains("qop=auth-int"));
+    assert!(header.contains(r#"response="9efd78e54f44652595b3649e667dbf6d9ba55e95c49f7d9eabc2464e29857937""#));
+
+    let parsed = parse_authorization(header).unwrap();
+    assert!(parsed.session);
+    assert!(parsed.verify("Mufasa", b"Circle of Life", "POST", b"hello"));
+    assert!(!parsed.verify("Mufasa", b"Circle of Life", "POST", b"hellO"));
+}
+
+#[test]
+fn test_authorization_escape() {
+    let mut out = [0u8; 512];
+
+    let challenge = parse_challenge(r#"Digest realm="my \"realm\"", qop="auth", nonce="nonce""#).unwrap();
+    let request = Request {
+        username: r#"a"b"#,
+        password: b"pw",
+        method: "GET",
+        uri: "/",
+        qop: Some(Qop::Auth),
+        body: b"",
+        cnonce: "cn",
+        nc: 1,
+    };
+    let header = challenge.authorization(&request, &mut out).unwrap();
+    assert_eq!(header, r#"Digest username="a\"b", realm="my \"realm\"", uri="/", algorithm=MD5, nonce="nonce", nc=00000001, cnonce="cn", qop=auth, response="eb93301e5ee889e1153df0a848a3840f""#);
+    assert!(parse_authorization(header).unwrap().verify(r#"a"b"#, b"pw", "GET", b""));
+}
+
+#[test]
+fn test_authorization_userhash() {
+    let mut out = [0u8; 512];
+
+    let challenge = parse_challenge(r#"Digest realm="api@example.org", qop="auth", algorithm=SHA-512-256, nonce="5TsQWLVdgBdmrQ0XsxbDODV+57QdFR34I9HAbC/RVvkK", userhash=true"#).unwrap();
+    let request = Request {
+        username: "Mufasa",
+        password: b"Secret, or not?",
+        method: "GET",
+        uri: "/doc/index.html",
+        qop: Some(Qop::Auth),
+        body: b"",
+        cnonce: "NTg6RKcb9boFIAS3KrFK9BGeh+iDa/sm6jUMp2wds69v",
+        nc: 1,
+    };
+    let header = challenge.authorization(&request, &mut out).unwrap();
+    assert_eq!(header, r#"Digest username="0f6bd1b4e5cf9aec865beb611400ae1ccdf947de59cfcebdc85e80d8704870a7", realm="api@example.org", uri="/doc/index.html", algorithm=SHA-512-256, nonce="5TsQWLVdgBdmrQ0XsxbDODV+57QdFR34I9HAbC/RVvkK", nc=00000001, cnonce="NTg6RKcb9boFIAS3KrFK9BGeh+iDa/sm6jUMp2wds69v", qop=auth, response="9116d0f2925749f200d742d6b87acbb761b3941ed19a6d46e5bdee14acaeaf1c", userhash=true"#);
+
+    let parsed = parse_authorization(header).unwrap();
+    assert!(parsed.userhash);
+    assert!(parsed.verify("Mufasa", b"Secret, or not?", "GET", b""));
+    assert!(!parsed.verify("Simba", b"Secret, or not?", "GET", b""));
+}
+
+#[test]
+fn test_authorization_long_values() {
+    let mut out = [0u8; 4096];
+
+    let realm = "r".repeat(300);
+    let nonce = "n".repeat(300);
+    let uri = format!("/search?q=\"{}\"", "x".repeat(600));
+    let header = format!(r#"Digest realm="{}", qop="auth", algorithm=SHA-256, nonce="{}""#, realm, nonce);
+    let challenge = parse_challenge(&header).unwrap();
+    let request = Request {
+        username: "Mufasa",
+        password: b"Circle of Life",
+        method: "GET",
+        uri: &uri,
+        qop: Some(Qop::Auth),
+        body: b"",
+        cnonce: RFC7616_CNONCE,
+        nc: 1,
+    };
+    let header = challenge.authorization(&request, &mut out).unwrap();
+
+    let ha1 = http_digest::ha1::<Sha256>("Mufasa", &realm, b"Circle of Life");
+    let ha2 = http_digest::ha2::<Sha256>("GET", &uri, None);
+    let expected = http_digest::response::<Sha256>(ha1.as_str(), &nonce, Some(Qop::Auth), 1, RFC7616_CNONCE, ha2.as_str());
+
+    let parsed = parse_authorization(header).unwrap();
+    assert_eq!(parsed.response, expected.as_str());
+    assert!(parsed.verify("Mufasa", b"Circle of Life", "GET", b""));
+    assert!(!parsed.verify("Mufasa", b"Circle of life", "GET", b""));
+}
+
+#[test]
+fn test_parse_authorization_invalid() {
+    assert!(parse_authorization(r#"Digest username="a", realm="r", uri="/", nonce="n""#).is_none());
+    assert!(parse_authorization(r#"Digest username="a", realm="r", uri="/", nonce="n", response="x", nc=1"#).is_none());
+    assert!(parse_authorization(r#"Digest username="a", realm="r", uri="/", nonce="n", response="x", qop=other"#).is_none());
+    assert!(parse_authorization(r#"Basic dXNlcjpwYXNz"#).is_none());
+}
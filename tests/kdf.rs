@@ -0,0 +1,198 @@
+use lhash::{DigestFmt, Digest, Sha1, Sha256, Sha384, Sha512};
+use lhash::kdf::{Counter, CounterLocation, kbkdf_counter, kbkdf_feedback, one_step_kdf, one_step_kdf_hmac, x963_kdf, mgf1, pbkdf2};
+
+mod common;
+use common::from_hex;
+
+fn digest_to_hex(input: impl AsRef<[u8]>) -> String {
+    DigestFmt(input).to_string()
+}
+
+fn check_counter<D: Digest>(key: &str, fixed: &str, counter: Counter, expected: &str) {
+    let mut out = vec![0; expected.len() / 2];
+    kbkdf_counter::<D>(&from_hex(key), &from_hex(fixed), counter, &mut out);
+    assert_eq!(digest_to_hex(&out), expected);
+}
+
+fn check_feedback<D: Digest>(key: &str, iv: &str, fixed: &str, counter: Option<Counter>, expected: &str) {
+    let mut out = vec![0; expected.len() / 2];
+    kbkdf_feedback::<D>(&from_hex(key), &from_hex(iv), &from_hex(fixed), counter, &mut out);
+    assert_eq!(digest_to_hex(&out), expected);
+}
+
+#[test]
+fn test_kbkdf_counter() {
+    //CAVP KBKDFCTR_gen.rsp [PRF=HMAC_SHA1] [CTRLOCATION=BEFORE_FIXED] [RLEN=8_BITS] COUNT=0
+    check_counter::<Sha1>(
+        "00a39bd547fb88b2d98727cf64c195c61e1cad6c",
+        "98132c1ffaf59ae5cbc0a3133d84c551bb97e0c75ecaddfc30056f6876f59803009bffc7d75c4ed46f40b8f80426750d15bc1ddb14ac5dcb69a68242",
+        Counter::new(8, CounterLocation::BeforeFixed),
+        "0611e1903609b47ad7a5fc2c82e47702",
+    );
+    //CAVP KBKDFCTR_gen.rsp [PRF=HMAC_SHA1] [CTRLOCATION=BEFORE_FIXED] [RLEN=8_BITS] COUNT=1
+    check_counter::<Sha1>(
+        "a39bdf744ed7e33fdec060c8736e9725179885a8",
+        "af71b44940acff98949ad17f1ca20e8fdb3957cacdcd41e9c591e18235019f90b9f8ee6e75700bcab2f8407525a104799b3e9725e27d738a9045e832",
+        Counter::new(8, CounterLocation::BeforeFixed),
+        "51dc4668947e3685099bc3b5f8527468",
+    );
+    //Remaining cases are output of pyca/cryptography KBKDFHMAC for same inputs
+    check_counter::<Sha256>(
+        "5d05d1c6f13e545ab9ae137bbce871467e809744cf958892b014557a654b8c71",
+        "1edda598fd05f574539d5559cdab2b953b4bc07f2bd440b5632755c1c6d8cab5ecd4ffe96a1174d948a3e1035a57cdffba6a479fcaf7e44f55a3a690",
+        Counter::default(),
+        "0b9bb20a1bd8142b93700cf7ea8a7a5f6e27a3ccdce8506e9a3affd51d52ae7e718a0b266c74cffe",
+    );
+    check_counter::<Sha256>(
+        "7d1e8015f61ecc75f523afd822f414aa05d40251de39d028b0a76e8131701bdf",
+        "ed9dc02eb55a28d69a3dee530d0fade3e7031ce475835057148af8bb880b161202c11fb329bc828360da9b5fbf9621f3fab1c620867e9b86e4451851",
+        Counter::new(16, CounterLocation::AfterFixed),
+        "b3c889b5a8adfa0786b5db15d11fcbdeaf96b676305115aa8f0a06d2c6ab5ea87e050f307d0a9bf1dc0e5ea37e3636bcd45c5c135ad518eed26325fb42a65b08",
+    );
+    check_counter::<Sha384>(
+        "adc3ba2e5f649beaaa00c569466b9e38e81fbbb764a68940f29462fdf61b0682",
+        "9d0f8ed3b81d8a62b7830d451d2945cad995ffd4361101719a6d45595d01011f9a3f5188b0b64fb39267038db9c2cde1b38f90fd0a2b8c02331ef145",
+        Counter::new(24, CounterLocation::MiddleFixed(10)),
+        "baf8d31b72c81df09477f3f79e7ce2e56e88262fc1cb56f673fd802020ac251fd53f7673234584f648e4e23685930f12899a",
+    );
+    check_counter::<Sha512>(
+        "5b72a2b0ccde020ca958fe9e0287f097e1ebfa11083b35aaec8453332261417d",
+        "0d27493fbdb7bddcc8f402a4379124308b06072dc5ff06e48a96715aa416cb919ecccaf2bd5b1b0524dd7228d397113acf8f4d40dd2676648b114362",
+        Counter::new(32, CounterLocation::AfterFixed),
+        "eb2cb2df44b4269191918bbb9a95fbca216f7a0f1b18ce305ddc7b56565570c7019bc094c8c0a694a15d43341949a5c8f3b02c91d6c1478ce761bcbc560a687ce1aa5656286db5f27f13b843c6b134606b79a8bea1cd8970ed36ed729bcdc4a2ce10ce4449ba0b5c8a4d71527fe529d720993a3e5dd6338063cc48162ab563852621abe7b740b8c37fe36ca6e543d82a0dda681a581dc8f17d708d44e64b3255",
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_kbkdf_counter_overflow() {
+    let mut out = [0u8; 20 * 256];
+    kbkdf_counter::<Sha1>(b"key", b"fixed", Counter::new(8, CounterLocation::BeforeFixed), &mut out);
+}
+
+#[test]
+fn test_kbkdf_feedback() {
+    //OpenSSL 3.5 KBKDF FEEDBACK with `use-l=0` and `use-separator=0`
+    check_feedback::<Sha256>(
+        "8165c4acbb8a5ebf4d856d2593b13db216bef9435d7ac5ae70bc8ea5c8fdb55a",
+        "",
+        "1500d9edc60efeb13948974e62cb2d08fd17ab079734eda991ef9650e2042873d48dfd2085c711fd",
+        Some(Counter::new(32, CounterLocation::BeforeFixed)),
+        "17ec0313666913f163434bd7cd0de7296680c66fde34b3a4a4c36fb66b4dc635155ffa02f1dbe75ed9a08d70b4525222dfff00e788afaa16e3a0b0fac58178b6f0b098558728b0c45a1c0b49e9f46fa6",
+    );
+    check_feedback::<Sha256>(
+        "8165c4acbb8a5ebf4d856d2593b13db216bef9435d7ac5ae70bc8ea5c8fdb55a",
+        "36710664d61b9295e5425ff00d65fbfa9071f3626c27b24f1189c014a6f531f5",
+        "1500d9edc60efeb13948974e62cb2d08fd17ab079734eda991ef9650e2042873d48dfd2085c711fd",
+        Some(Counter::new(32, CounterLocation::BeforeFixed)),
+        "e8c7249131bc459e2f2c4018b76786203159cd30b245a68872c3dc43372310d680e39155717149e857a5d8558ff93ac32ea41528c5299ca9373f3eee5d4010a2",
+    );
+    //OpenSSL accepts only 32 bit counter before fixed input, so remaining cases are output of straightforward SP 800-108 section 5.2 implementation in Python
+    check_feedback::<Sha256>(
+        "8165c4acbb8a5ebf4d856d2593b13db216bef9435d7ac5ae70bc8ea5c8fdb55a",
+        "36710664d61b9295e5425ff00d65fbfa9071f3626c27b24f1189c014a6f531f5",
+        "1500d9edc60efeb13948974e62cb2d08fd17ab079734eda991ef9650e2042873d48dfd2085c711fd",
+        Some(Counter::new(8, CounterLocation::BeforeIter)),
+        "379b8676b9bfd1602daee7956b16ab25b944c55246c1c68c91da035d29024bfac5dd92f9f82778e6fd7a8a1ff2216587030c68f9b674507ce35dd0d19dda6443",
+    );
+    check_feedback::<Sha1>(
+        "87d9fa1569cc93224f3a3e6ae1534600d925d299a49472e8f7dac517d74eeb32",
+        "794f3f4d29aba753a2290ca9e6d8b85e797539dc",
+        "00d81784deaaf71ce771a4ce24cf0db1108c421ba18b21c9d0381b339d2ebdfe1e135966d25a8ff2",
+        Some(Counter::new(16, CounterLocation::AfterFixed)),
+        "038c88b0a2d693568711db27ae055e795bf83a2d8e2aaff0ab6b6db487b8a2d07e132c552d4bf574ca153785b5655fedc288",
+    );
+    check_feedback::<Sha512>(
+        "8be881c61f7607a6bc834bb7067dc2750fe7d37fe6c67996bef758f038c826c4",
+        "ee51c7c11877121f47e77c116a3e5854abc8a2ba20661959bd0b3a192ecf9d1cb1252c636af5eef765a03f9201b1d2c567abbe098e573bb6787e7aaede5b2feb",
+        "67424d7e6ed6adad2623eba023d130c7e4da3fc2ff17f48da0f6d4f9cf5c662cbc8d9942c11dc09d",
+        None,
+        "fcee1429b6b7a6b0166ae1d3e5ed9ffbfbb9afc75b6f015cbead78a647628105054d55486518dd0d5a5b7efcf39717498241fe5672a8deef191aa7436cf8566ccc40463586f8c447fdcef717696374dfd9fee4520618645be5ee538d204018236dc9f8e7",
+    );
+}
+
+#[test]
+fn test_one_step_kdf() {
+    //Output of pyca/cryptography ConcatKDFHash and ConcatKDFHMAC for same inputs
+    let secret = from_hex("f8e8e742b81b806604fc01df885572a777edfa92cf5e81ce6347f49d84780545");
+    let fixed_info = from_hex("9daa3b5dbeb39e8a2681300a670cad0868c1d2ba");
+
+    let mut out = [0u8; 70];
+    one_step_kdf::<Sha256>(&secret, &fixed_info, &mut out);
+    assert_eq!(digest_to_hex(&out[..]), "fb7bcb5471ba3f157d693785b1a154fb73c201a3d1fb8e6ae6b839a87755a709dfe43744af4546d796a30e9ee52e9b44c63d794ac02702299079efe5a4f40416d1656fc06a5b");
+
+    let mut out = [0u8; 32];
+    one_step_kdf::<Sha1>(&secret, &fixed_info, &mut out);
+    assert_eq!(digest_to_hex(out), "cb07fd6e5999da8fd4952444c84a86860915f2dc41cec1fbb7a19edf69603d16");
+
+    let mut out = [0u8; 70];
+    one_step_kdf_hmac::<Sha256>(&[], &secret, &fixed_info, &mut out);
+    assert_eq!(digest_to_hex(&out[..]), "0ef4d517b03e84d29742605606d675bd07d91d863c744c3509b6ad9d25dafc12e73156e4271b1d9fdc2135d8be4eb6d786f03d4ab724819f818c2674c84a554750d76644cafa");
+
+    let mut out = [0u8; 80];
+    one_step_kdf_hmac::<Sha512>(&from_hex("2bc990059473448e6ebd442aa16bfaa0"), &secret, &fixed_info, &mut out);
+    assert_eq!(digest_to_hex(&out[..]), "62d4247956542bacb87c0822a683d20995bd5cb9f8395589a6aee9b4c83b99f4b15faa8fa116876d7d34da2bb94ddd6b5922f0edf3becd5bc575b2d6558933a5dd1b7f56fdbf8360e61dd388b0057aab");
+}
+
+#[test]
+fn test_x963_kdf() {
+    //CAVS 12.0 ansx963_2001.rsp [SHA-1] [shared secret length = 192] [SharedInfo length = 0] [key data length = 128] COUNT = 0
+    let mut out = [0u8; 16];
+    x963_kdf::<Sha1>(&from_hex("1c7d7b5f0597b03d06a018466ed1a93e30ed4b04dc64ccdd"), &[], &mut out);
+    assert_eq!(digest_to_hex(out), "bf71dffd8f4d99223936beb46fee8ccc");
+
+    //pyca/cryptography tests/hazmat/primitives/test_x963kdf.py
+    let mut out = [0u8; 16];
+    x963_kdf::<Sha256>(&from_hex("96c05619d56c328ab95fe84b18264b08725b85e33fd34f08"), &[], &mut out);
+    assert_eq!(digest_to_hex(out), "443024c3dae66b95e6f5670601558f71");
+
+    let mut out = [0u8; 128];
+    x963_kdf::<Sha256>(&from_hex("22518b10e70f2a3f243810ae3254139efbee04aa57c7af7d"), &from_hex("75eef81aa3041e33b80971203d2c0c52"), &mut out);
+    assert_eq!(digest_to_hex(&out[..]), "c498af77161cc59f2962b9a713e2b215152d139766ce34a776df11866a69bf2e52a13d9c7c6fc878c50c5ea0bc7b00e0da2447cfd874f6cf92f30d0097111485500c90c3af8b487872d04685d14c8d1dc8d7fa08beb0ce0ababc11f0bd496269142d43525a78e5bc79a17f59676a5706dc54d54d4d1f0bd7e386128ec26afc21");
+}
+
+#[test]
+fn test_mgf1() {
+    //MGF1 examples from Wikipedia article "Mask generation function"
+    let tests: [(&'static [u8], usize, &'static str); 3] = [
+        (b"foo", 3, "1ac907"),
+        (b"foo", 5, "1ac9075cd4"),
+        (b"bar", 5, "bc0c655e01"),
+    ];
+
+    for (seed, len, expected) in tests.iter() {
+        let mut mask = vec![0; *len];
+        mgf1::<Sha1>(seed, &mut mask);
+        assert_eq!(digest_to_hex(&mask), *expected);
+    }
+
+    let mut mask = [0u8; 50];
+    mgf1::<Sha256>(b"bar", &mut mask);
+    assert_eq!(digest_to_hex(&mask[..]), "382576a7841021cc28fc4c0948753fb8312090cea942ea4c4e735d10dc724b155f9f6069f289d61daca0cb814502ef04eae1");
+}
+
+fn check_pbkdf2<D: Digest>(password: &[u8], salt: &[u8], iterations: u32, expected: &str) {
+    let mut out = vec![0u8; expected.len() / 2];
+    pbkdf2::<D>(password, salt, iterations, &mut out);
+    assert_eq!(digest_to_hex(&out), expected);
+}
+
+#[test]
+fn test_pbkdf2() {
+    //RFC 6070 section 2
+    check_pbkdf2::<Sha1>(b"password", b"salt", 1, "0c60c80f961f0e71f3a9b524af6012062fe037a6");
+    check_pbkdf2::<Sha1>(b"password", b"salt", 2, "ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957");
+    check_pbkdf2::<Sha1>(b"password", b"salt", 4096, "4b007901b765489abead49d926f721d065a429c1");
+    check_pbkdf2::<Sha1>(b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, "3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038");
+
+    //Python `hashlib.pbkdf2_hmac("sha256", ...)`
+    check_pbkdf2::<Sha256>(b"password", b"salt", 1, "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b");
+    check_pbkdf2::<Sha256>(b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, "348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9");
+}
+
+#[test]
+#[should_panic]
+fn test_pbkdf2_zero_iterations() {
+    let mut out = [0u8; 20];
+    pbkdf2::<Sha1>(b"password", b"salt", 0, &mut out);
+}
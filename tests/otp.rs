@@ -0,0 +1,57 @@
+use lhash::{Digest, Md5, Sha1, Sha256, Sha512};
+use lhash::otp::{hotp, totp, verify_hotp, verify_totp};
+
+#[test]
+fn test_hotp() {
+    //RFC 4226 Appendix D
+    let secret = b"12345678901234567890";
+    let expected = [755224, 287082, 359152, 969429, 338314, 254676, 287922, 162583, 399871, 520489];
+
+    for (counter, expected) in expected.iter().enumerate() {
+        assert_eq!(hotp::<Sha1>(secret, counter as u64, 6), *expected);
+    }
+}
+
+#[test]
+fn test_verify_hotp() {
+    let secret = b"12345678901234567890";
+
+    assert_eq!(verify_hotp::<Sha1>(secret, 0, 6, 0, 755224), Some(0));
+    assert_eq!(verify_hotp::<Sha1>(secret, 0, 6, 0, 287082), None);
+    assert_eq!(verify_hotp::<Sha1>(secret, 0, 6, 5, 254676), Some(5));
+    assert_eq!(verify_hotp::<Sha1>(secret, 0, 6, 4, 254676), None);
+    assert_eq!(verify_hotp::<Sha1>(secret, 6, 6, 10, 254676), None);
+    assert_eq!(verify_hotp::<Sha1>(secret, u64::MAX, 6, 10, 254676), None);
+}
+
+fn check_totp<D: Digest>(secret: &[u8], expected: &[u32; 6]) {
+    let times = [59, 1111111109, 1111111111, 1234567890, 2000000000, 20000000000];
+
+    for (time, expected) in times.iter().zip(expected.iter()) {
+        assert_eq!(totp::<D>(secret, *time, 30, 0, 8), *expected);
+        assert_eq!(verify_totp::<D>(secret, *time, 30, 0, 8, 0, *expected), Some(time / 30));
+        assert_eq!(verify_totp::<D>(secret, time + 30, 30, 0, 8, 1, *expected), Some(time / 30));
+        assert_eq!(verify_totp::<D>(secret, time - 30, 30, 0, 8, 1, *expected), Some(time / 30));
+        assert_eq!(verify_totp::<D>(secret, time + 60, 30, 0, 8, 1, *expected), None);
+    }
+}
+
+#[test]
+fn test_totp() {
+    //RFC 6238 Appendix B
+    check_totp::<Sha1>(b"12345678901234567890", &[94287082, 7081804, 14050471, 89005924, 69279037, 65353130]);
+    check_totp::<Sha256>(b"12345678901234567890123456789012", &[46119246, 68084774, 67062674, 91819424, 90698825, 77737706]);
+    check_totp::<Sha512>(b"1234567890123456789012345678901234567890123456789012345678901234", &[90693936, 25091201, 99943326, 93441116, 38618901, 47863826]);
+}
+
+#[test]
+#[should_panic(expected = "Digest output must be at least 20 bytes")]
+fn test_hotp_short_digest() {
+    hotp::<Md5>(b"12345678901234567890", 0, 6);
+}
+
+#[test]
+#[should_panic(expected = "Digest output must be at least 20 bytes")]
+fn test_verify_totp_short_digest() {
+    verify_totp::<Md5>(b"12345678901234567890", 59, 30, 0, 6, 1, 0);
+}
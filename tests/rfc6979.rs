@@ -0,0 +1,48 @@
+use lhash::{DigestFmt, Digest, Sha1, Sha256, Sha512, rfc6979, Rfc6979};
+
+mod common;
+use common::from_hex;
+
+fn digest_to_hex(input: impl AsRef<[u8]>) -> String {
+    DigestFmt(input).to_string()
+}
+
+fn hash<D: Digest>(input: &[u8]) -> D::OutputType {
+    let mut algo = D::new();
+    algo.update(input);
+    algo.result()
+}
+
+const P256_ORDER: &str = "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551";
+const P256_KEY: &str = "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+
+fn p256_nonce<D: Digest>(message: &str) -> String {
+    let hash = hash::<D>(message.as_bytes());
+    let nonce = rfc6979::<D>(&from_hex(P256_KEY), hash.as_ref(), &from_hex(P256_ORDER)).next().unwrap();
+    digest_to_hex(nonce)
+}
+
+#[test]
+fn test_p256() {
+    //RFC 6979 A.2.5
+    assert_eq!(p256_nonce::<Sha1>("sample"), "882905f1227fd620fbf2abf21244f0ba83d0dc3a9103dbbee43a1fb858109db4");
+    assert_eq!(p256_nonce::<Sha256>("sample"), "a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60");
+    assert_eq!(p256_nonce::<Sha512>("sample"), "5fa81c63109badb88c1f367b47da606da28cad69aa22c4fe6ad7df73a7173aa5");
+
+    assert_eq!(p256_nonce::<Sha1>("test"), "8c9520267c55d6b980df741e56b4adee114d84fbfa2e62137954164028632a2e");
+    assert_eq!(p256_nonce::<Sha256>("test"), "d16b6ae827f17175e040871a1c7ec3500192c4c92677336ec2537acaee0008e0");
+    assert_eq!(p256_nonce::<Sha512>("test"), "6915d11632aca3c40d5d51c08daf9c555933819548784480e93499000d9f0b7f");
+}
+
+#[test]
+fn test_truncated_order() {
+    //RFC 6979 A.1.2, 163-bit order, where hash and candidates must be truncated.
+    let order = from_hex("04000000000000000000020108a2e0cc0d99f8a5ef");
+    let key = from_hex("009a4d6792295a7f730fc3f2b49cbc0f62e862272f");
+    let hash = hash::<Sha256>(b"sample");
+
+    let mut nonces = Rfc6979::<Sha256>::new(&key, hash.as_ref(), &order, &[]);
+    assert_eq!(digest_to_hex(nonces.next().unwrap()), "023af4074c90a02b3fe61d286d5c87f425e6bdd81b");
+    assert_eq!(digest_to_hex(nonces.next().unwrap()), "0108f6a59fa76a12fc133dd7b9fad249cdb6fca97b");
+    assert_eq!(digest_to_hex(nonces.next().unwrap()), "02bec29e05005e8a48c8d70fffbbd796bf191b21ea");
+}
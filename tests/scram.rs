@@ -0,0 +1,130 @@
+use lhash::{Digest, Sha1, Sha256};
+use lhash::scram::{Client, Credentials, ScramError, Server};
+
+fn check_exchange<D: Digest>(nonce: &str, server_nonce: &str, salt: &[u8], server_first: &str, client_final: &str, server_final: &str) {
+    let mut buf = [0u8; 256];
+    let mut client_buf = [0u8; 256];
+    let mut server_buf = [0u8; 256];
+
+    let client = Client::<D>::new("user", b"pencil", nonce).unwrap();
+    let client_first = client.client_first(&mut buf).unwrap();
+    assert_eq!(client_first, format!("n,,n=user,r={}", nonce));
+
+    let server = Server::<D>::new(client_first).unwrap();
+    assert_eq!(server.username(), "user");
+    assert_eq!(server.authzid(), None);
+    let credentials = Credentials::<D>::new(b"pencil", salt, 4096);
+    let state = server.server_first(server_nonce, salt, 4096, &credentials, &mut server_buf).unwrap();
+    assert_eq!(state.server_first(), server_first);
+
+    let (verifier, actual_final) = client.client_final(state.server_first(), &mut client_buf).unwrap();
+    assert_eq!(actual_final, client_final);
+
+    let mut out = [0u8; 128];
+    let actual_server_final = state.server_final(actual_final, &mut out).unwrap();
+    assert_eq!(actual_server_final, server_final);
+    assert_eq!(verifier.verify(actual_server_final), Ok(()));
+}
+
+#[test]
+fn test_scram_sha1() {
+    //RFC 5802 section 5
+    check_exchange::<Sha1>("fyko+d2lbbFgONRv9qkxdawL", "3rfcNHYJY1ZVvWVs7j", &[0x41, 0x25, 0xc2, 0x47, 0xe4, 0x3a, 0xb1, 0xe9, 0x3c, 0x6d, 0xff, 0x76],
+                           "r=fyko+d2lbbFgONRv9qkxdawL3rfcNHYJY1ZVvWVs7j,s=QSXCR+Q6sek8bf92,i=4096",
+                           "c=biws,r=fyko+d2lbbFgONRv9qkxdawL3rfcNHYJY1ZVvWVs7j,p=v0X8v3Bz2T0CJGbJQyF0X+HI4Ts=",
+                           "v=rmF9pqV8S7suAoZWja4dJRkFsKQ=");
+}
+
+#[test]
+fn test_scram_sha256() {
+    //RFC 7677 section 3
+    check_exchange::<Sha256>("rOprNGfwEbeRWgbNEkqO", "%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0", &[0x5b, 0x6d, 0x99, 0x68, 0x9d, 0x12, 0x35, 0x8e, 0xec, 0xa0, 0x4b, 0x14, 0x12, 0x36, 0xfa, 0x81],
+                             "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096",
+                             "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=",
+                             "v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=");
+}
+
+#[test]
+fn test_scram_username() {
+    let mut buf = [0u8; 64];
+    let client = Client::<Sha256>::new("a,b=c", b"pencil", "nonce").unwrap();
+    let client_first = client.client_first(&mut buf).unwrap();
+    assert_eq!(client_first, "n,,n=a=2Cb=3Dc,r=nonce");
+
+    let server = Server::<Sha256>::new(client_first).unwrap();
+    assert_eq!(server.username(), "a=2Cb=3Dc");
+    let mut out = [0u8; 16];
+    assert_eq!(server.decode_username(&mut out), Ok("a,b=c"));
+
+    let server = Server::<Sha256>::new("n,a=admin,n=user,r=nonce").unwrap();
+    assert_eq!(server.authzid(), Some("admin"));
+    assert!(Server::<Sha256>::new("n,,n=a=2Xb,r=nonce").unwrap().decode_username(&mut out).is_err());
+}
+
+#[test]
+fn test_scram_client_errors() {
+    let mut buf = [0u8; 256];
+    assert_eq!(Client::<Sha1>::new("user", b"pencil", "bad,nonce").err(), Some(ScramError::InvalidNonce));
+    assert_eq!(Client::<Sha1>::new("user", b"pencil", "").err(), Some(ScramError::InvalidNonce));
+
+    let client = Client::<Sha1>::new("user", b"pencil", "fyko+d2lbbFgONRv9qkxdawL").unwrap();
+    assert_eq!(client.client_first(&mut buf[..8]), Err(ScramError::BufferTooSmall));
+    assert_eq!(client.client_final("r=other3rfcNHYJY1ZVvWVs7j,s=QSXCR+Q6sek8bf92,i=4096", &mut buf).err(), Some(ScramError::InvalidNonce));
+    assert_eq!(client.client_final("r=fyko+d2lbbFgONRv9qkxdawL,s=QSXCR+Q6sek8bf92,i=4096", &mut buf).err(), Some(ScramError::InvalidNonce));
+    assert_eq!(client.client_final("r=fyko+d2lbbFgONRv9qkxdawL3rfc,s=QSXCR+Q6sek8bf92,i=0", &mut buf).err(), Some(ScramError::InvalidMessage));
+    assert_eq!(client.client_final("r=fyko+d2lbbFgONRv9qkxdawL3rfc,s=QSXCR+Q6sek8bf92", &mut buf).err(), Some(ScramError::InvalidMessage));
+    assert_eq!(client.client_final("m=ext,r=fyko+d2lbbFgONRv9qkxdawL3rfc,s=QSXCR+Q6sek8bf92,i=1", &mut buf).err(), Some(ScramError::Unsupported));
+
+    let (verifier, _) = client.client_final("r=fyko+d2lbbFgONRv9qkxdawL3rfcNHYJY1ZVvWVs7j,s=QSXCR+Q6sek8bf92,i=4096", &mut buf).unwrap();
+    assert_eq!(verifier.verify("v=rmF9pqV8S7suAoZWja4dJRkFsKQ="), Ok(()));
+    assert_eq!(verifier.verify("v=AmF9pqV8S7suAoZWja4dJRkFsKQ="), Err(ScramError::AuthenticationFailed));
+    assert_eq!(verifier.verify("v=rmF9pqV8S7suAoZWja4dJRkF"), Err(ScramError::InvalidMessage));
+    assert_eq!(verifier.verify("e=invalid-proof"), Err(ScramError::ServerError));
+}
+
+#[test]
+fn test_scram_server_errors() {
+    let mut buf = [0u8; 256];
+    let mut out = [0u8; 128];
+    let salt = b"salt";
+    let credentials = Credentials::<Sha256>::new(b"pencil", salt, 4096);
+
+    assert_eq!(Server::<Sha256>::new("p=tls-unique,,n=user,r=nonce").err(), Some(ScramError::Unsupported));
+    assert_eq!(Server::<Sha256>::new("n,,m=ext,n=user,r=nonce").err(), Some(ScramError::Unsupported));
+    assert_eq!(Server::<Sha256>::new("x,,n=user,r=nonce").err(), Some(ScramError::InvalidMessage));
+    assert_eq!(Server::<Sha256>::new("n,,n=user").err(), Some(ScramError::InvalidMessage));
+
+    let server = Server::<Sha256>::new("n,,n=user,r=nonce").unwrap();
+    let state = server.server_first("server", salt, 4096, &credentials, &mut buf).unwrap();
+    assert_eq!(state.server_first(), "r=nonceserver,s=c2FsdA==,i=4096");
+
+    let mut client_buf = [0u8; 256];
+    let client = Client::<Sha256>::new("user", b"wrong", "nonce").unwrap();
+    let (_, client_final) = client.client_final(state.server_first(), &mut client_buf).unwrap();
+    assert_eq!(state.server_final(client_final, &mut out), Err(ScramError::AuthenticationFailed));
+
+    let client = Client::<Sha256>::new("user", b"pencil", "nonce").unwrap();
+    let (_, client_final) = client.client_final(state.server_first(), &mut client_buf).unwrap();
+    assert!(state.server_final(client_final, &mut out).is_ok());
+    assert_eq!(state.server_final(client_final, &mut out[..8]), Err(ScramError::BufferTooSmall));
+
+    let tampered = client_final.replace("c=biws", "c=eSws");
+    assert_eq!(state.server_final(&tampered, &mut out), Err(ScramError::AuthenticationFailed));
+    let tampered = client_final.replace("r=nonceserver", "r=nonceother");
+    assert_eq!(state.server_final(&tampered, &mut out), Err(ScramError::InvalidNonce));
+}
+
+#[test]
+#[should_panic]
+fn test_scram_credentials_zero_iterations() {
+    Credentials::<Sha256>::new(b"pencil", b"salt", 0);
+}
+
+#[test]
+#[should_panic]
+fn test_scram_server_zero_iterations() {
+    let mut buf = [0u8; 256];
+    let credentials = Credentials::<Sha256>::new(b"pencil", b"salt", 4096);
+    let server = Server::<Sha256>::new("n,,n=user,r=nonce").unwrap();
+    let _ = server.server_first("server", b"salt", 0, &credentials, &mut buf);
+}
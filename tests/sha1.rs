@@ -1,4 +1,7 @@
-use lhash::{DigestFmt, Sha1, hmac};
+use lhash::{Digest, DigestFmt, Sha1, Sha1State, StateError, hmac, hmac_sha1};
+
+#[macro_use]
+mod common;
 
 fn digest_to_hex(input: impl AsRef<[u8]>) -> String {
     DigestFmt(input).to_string()
@@ -66,3 +69,111 @@ fn test_hmac() {
         assert_eq!(hash, *expected);
     }
 }
+
+#[test]
+fn test_const_hmac() {
+    const HASHES: [[u8; 20]; 4] = [
+        hmac_sha1(b"Hi There", &[0x0B; 20]),
+        hmac_sha1(b"what do ya want for nothing?", b"Jefe"),
+        hmac_sha1(&[0xDD; 50], &[0xAA; 20]),
+        hmac_sha1(b"Test Using Larger Than Block-Size Key - Hash Key First", &[0xAA; 80]),
+    ];
+    let expected = [
+        "b617318655057264e28bc0b6fb378c8ef146be00",
+        "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+        "125d7342b9ac11cd91a39af48aa17b4f63f175d3",
+        "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+    ];
+
+    for (hash, expected) in HASHES.iter().zip(expected.iter()) {
+        assert_eq!(digest_to_hex(hash), *expected);
+    }
+}
+
+//1.3.14.3.2.26
+test_metadata!(Sha1, 20, 64, "SHA-1", [0x2b, 0x0e, 0x03, 0x02, 0x1a]);
+
+test_finalize!(Sha1, lhash::sha1);
+
+test_traits!(Sha1, lhash::sha1);
+
+test_export_state!(Sha1, lhash::sha1, 94);
+
+#[test]
+fn test_length_limit() {
+    let max_len: u64 = (1 << 61) - 1;
+    let mut exported = Sha1::new().export_state();
+    exported[2..10].copy_from_slice(&(max_len - 1).to_be_bytes());
+
+    let mut hasher = Sha1::import_state(&exported).expect("to import");
+    hasher.update(b"a");
+    hasher.update(b"");
+    hasher.result();
+
+    exported[2..10].copy_from_slice(&(max_len + 1).to_be_bytes());
+    assert_eq!(Sha1::import_state(&exported).unwrap_err(), StateError::InvalidLength);
+}
+
+#[test]
+#[should_panic(expected = "Input length exceeds SHA-1 limit")]
+fn test_length_overflow() {
+    let max_len: u64 = (1 << 61) - 1;
+    let mut exported = Sha1::new().export_state();
+    exported[2..10].copy_from_slice(&(max_len - 1).to_be_bytes());
+
+    let mut hasher = Sha1::import_state(&exported).expect("to import");
+    hasher.update(b"ab");
+}
+
+#[test]
+fn test_update_bits() {
+    //Bit-oriented messages of NIST examples, with expected values cross-checked against bitwise reference implementation
+    let tests: [(&[u8], usize, &str); 4] = [
+        (&[0x98], 5, "29826b003b906e660eff4027ce98af3531ac75ba"),
+        (&[0x00], 1, "bb6b3e18f0115b57925241676f5b1ae88747b08a"),
+        (&[0x80], 1, "59c4526aa2cc59f9a5f56b5579ba7108e7ccb61a"),
+        (&[0x49, 0xb2, 0xae, 0xc2, 0x59, 0x4b, 0xbe, 0x3a, 0x3b, 0x11, 0x75, 0x42, 0xd9, 0x4a, 0xc8, 0x80], 123, "6239781e03729919c01955b3ffa8acb60b988340"),
+    ];
+
+    for (data, bit_len, expected) in tests.iter() {
+        let mut hasher = Sha1::new();
+        hasher.update_bits(data, *bit_len);
+        assert_eq!(digest_to_hex(hasher.result()), *expected);
+    }
+
+    //Unused low bits of partial byte are ignored
+    let mut hasher = Sha1::new();
+    hasher.update_bits(&[0x9F], 5);
+    assert_eq!(digest_to_hex(hasher.result()), tests[0].2);
+
+    let mut data = [0u8; 200];
+    for (idx, byte) in data.iter_mut().enumerate() {
+        *byte = idx as u8;
+    }
+    let tests = [
+        (&data[1..65], 511, "26e520b00000f2e345668d70e843e0d4321e076e"),
+        (&data[1..65], 505, "f57761a8c679b610ef169cc6a10903bf9a37c446"),
+        (&data[..], 1001, "af2d67af2429909b1a71dc3a7f62419beda4d138"),
+    ];
+
+    for (data, bit_len, expected) in tests.iter() {
+        let mut hasher = Sha1::new();
+        hasher.update(&data[..3]);
+        hasher.update_bits(&data[3..], bit_len - 24);
+        assert_eq!(digest_to_hex(hasher.result()), *expected);
+    }
+
+    let mut hasher = Sha1::new();
+    hasher.update_bits(&data[..3], 24);
+    assert_eq!(hasher.result(), lhash::sha1(&data[..3]));
+}
+
+#[test]
+#[should_panic(expected = "Input after partial byte")]
+fn test_update_after_partial_byte() {
+    let mut hasher = Sha1::new();
+    hasher.update_bits(&[0x98], 5);
+    hasher.update(b"a");
+}
+
+test_compress_blocks!(Sha1, Sha1State, lhash::sha1, (3u64 * 8).to_be_bytes());
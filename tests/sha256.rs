@@ -1,4 +1,7 @@
-use lhash::{DigestFmt, Sha256, hmac};
+use lhash::{ct_eq, ct_eq_truncated, Digest, DigestFmt, Hmac, HmacKey, Sha256, Sha256State, StateError, hmac, hmac_sha256};
+
+#[macro_use]
+mod common;
 
 fn digest_to_hex(input: impl AsRef<[u8]>) -> String {
     DigestFmt(input).to_string()
@@ -60,3 +63,254 @@ fn test_hmac() {
         assert_eq!(hash, *expected);
     }
 }
+
+#[test]
+fn test_hmac_incremental() {
+    let tests: [(&'static [u8], &'static [u8], &'static str); 3] = [
+        (&[0x0B; 20], b"Hi There", "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"),
+        (b"Jefe", b"what do ya want for nothing?", "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"),
+        (&[0xAA; 131], b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.", "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2"),
+    ];
+
+    for (key, data, expected) in tests.iter() {
+        let mut algo = Hmac::<Sha256>::new(key);
+        for chunk in data.chunks(7) {
+            algo.update(chunk);
+        }
+        let hash = algo.result();
+        assert_eq!(digest_to_hex(hash), *expected);
+
+        algo.reset();
+        algo.update(data);
+        assert!(algo.verify(&hash));
+        algo.reset();
+        algo.update(data);
+        assert!(!algo.verify(&hash[..16]));
+        algo.reset();
+        algo.update(&data[1..]);
+        assert!(!algo.verify(&hash));
+
+        let mut algo = HmacKey::<Sha256>::new(key).hmac();
+        algo.update(data);
+        assert_eq!(algo.result(), hash);
+    }
+
+    let algo = <Hmac<Sha256> as Digest>::new();
+    assert_eq!(digest_to_hex(algo.result()), "b613679a0814d9ec772f95d778c35fc5ff1697c493715653c6c712144292c5ad");
+
+    //HMAC over HMAC with empty key as hash function
+    let hash = hmac::<Hmac<Sha256>>(b"The quick brown fox jumps over the lazy dog", b"key");
+    assert_eq!(digest_to_hex(hash), "a211d048fe570169aa4026258f02d20027273665ddbfbc22b2d5138c7ac88a2b");
+}
+
+#[test]
+fn test_const_hmac() {
+    const HASHES: [[u8; 32]; 4] = [
+        hmac_sha256(b"Hi There", &[0x0B; 20]),
+        hmac_sha256(b"what do ya want for nothing?", b"Jefe"),
+        hmac_sha256(&[0xDD; 50], &[0xAA; 20]),
+        hmac_sha256(b"Test Using Larger Than Block-Size Key - Hash Key First", &[0xAA; 131]),
+    ];
+    let expected = [
+        "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+        "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+    ];
+
+    for (hash, expected) in HASHES.iter().zip(expected.iter()) {
+        assert_eq!(digest_to_hex(hash), *expected);
+    }
+}
+
+#[test]
+fn test_hmac_verify() {
+    //RFC 4231 4.7 with truncation to 128 bits
+    let key = HmacKey::<Sha256>::new(&[0x0C; 20]);
+    let data = b"Test With Truncation";
+    let tag = key.sign(data);
+    assert_eq!(digest_to_hex(&tag[..16]), "a3b6167473100ee06e0c796c2955552b");
+
+    assert!(key.verify(data, &tag));
+    assert!(!key.verify(data, &tag[..16]));
+    assert!(!key.verify(&data[1..], &tag));
+
+    assert!(key.verify_truncated(data, &tag, 16));
+    assert!(key.verify_truncated(data, &tag[..16], 16));
+    assert!(!key.verify_truncated(data, &tag[..15], 16));
+    assert!(!key.verify_truncated(&data[1..], &tag[..16], 16));
+
+    let mut algo = key.hmac();
+    algo.update(data);
+    assert!(algo.verify_truncated(&tag[..20], 16));
+
+    let mut bad_tag = tag;
+    bad_tag[31] ^= 1;
+    assert!(!key.verify(data, &bad_tag));
+    assert!(key.verify_truncated(data, &bad_tag[..31], 16));
+    assert!(!key.verify_truncated(data, &bad_tag, 16));
+}
+
+#[test]
+fn test_ct_eq() {
+    assert!(ct_eq(b"", b""));
+    assert!(ct_eq(b"abc", b"abc"));
+    assert!(!ct_eq(b"abc", b"abd"));
+    assert!(!ct_eq(b"abc", b"ab"));
+    assert!(!ct_eq(b"\x80bc", b"abc"));
+
+    assert!(ct_eq_truncated(b"abcdef", b"abcd", 4));
+    assert!(ct_eq_truncated(b"abcdef", b"abcdef", 4));
+    assert!(!ct_eq_truncated(b"abcdef", b"abc", 4));
+    assert!(!ct_eq_truncated(b"abcdef", b"abcdefg", 4));
+    assert!(!ct_eq_truncated(b"abcdef", b"abce", 4));
+}
+
+#[test]
+#[should_panic]
+fn test_ct_eq_truncated_zero_min_len() {
+    ct_eq_truncated(b"abcdef", b"", 0);
+}
+
+//2.16.840.1.101.3.4.2.1
+test_metadata!(Sha256, 32, 64, "SHA-256", [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01]);
+
+test_finalize!(Sha256, lhash::sha256);
+
+#[test]
+#[should_panic]
+fn test_finalize_into_wrong_size() {
+    Sha256::new().finalize_into(&mut [0u8; 16]);
+}
+
+#[test]
+fn test_hmac_finalize() {
+    let key = HmacKey::<Sha256>::new(b"key");
+    let mut algo = key.hmac();
+    algo.update(b"The quick brown fox ");
+    assert_eq!(algo.result(), key.sign(b"The quick brown fox "));
+    algo.update(b"jumps over the lazy dog");
+    assert_eq!(digest_to_hex(algo.finalize_reset()), "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8");
+    assert_eq!(algo.result(), key.sign(b""));
+}
+
+test_traits!(Sha256, lhash::sha256);
+
+#[test]
+fn test_hmac_debug() {
+    let key = HmacKey::<Sha256>::new(b"secret");
+    assert_eq!(format!("{:?}", key), "HmacKey { algorithm: \"SHA-256\", .. }");
+    assert_eq!(format!("{:?}", key.hmac()), "Hmac { algorithm: \"SHA-256\", .. }");
+}
+
+test_export_state!(Sha256, lhash::sha256, 106);
+
+#[test]
+fn test_import_state_errors() {
+    let mut hasher = Sha256::new();
+    hasher.update(b"abc");
+    let exported = hasher.export_state();
+
+    //Buffered input is kept, but stale bytes of buffer are not
+    assert_eq!(&exported[42..45], b"abc");
+    assert!(exported[45..].iter().all(|byte| *byte == 0));
+
+    assert_eq!(Sha256::import_state(&exported[..105]).unwrap_err(), StateError::InvalidSize);
+    let mut invalid = exported;
+    invalid[0] = 2;
+    assert_eq!(Sha256::import_state(&invalid).unwrap_err(), StateError::UnsupportedVersion);
+    let mut invalid = exported;
+    invalid[1] = 4;
+    assert_eq!(Sha256::import_state(&invalid).unwrap_err(), StateError::AlgorithmMismatch);
+}
+
+#[test]
+fn test_length_limit() {
+    let max_len: u64 = (1 << 61) - 1;
+    let mut exported = Sha256::new().export_state();
+    exported[2..10].copy_from_slice(&(max_len - 1).to_be_bytes());
+
+    let mut hasher = Sha256::import_state(&exported).expect("to import");
+    hasher.update(b"a");
+    hasher.update(b"");
+    hasher.result();
+
+    exported[2..10].copy_from_slice(&(max_len + 1).to_be_bytes());
+    assert_eq!(Sha256::import_state(&exported).unwrap_err(), StateError::InvalidLength);
+}
+
+#[test]
+#[should_panic(expected = "Input length exceeds SHA-256 limit")]
+fn test_length_overflow() {
+    let max_len: u64 = (1 << 61) - 1;
+    let mut exported = Sha256::new().export_state();
+    exported[2..10].copy_from_slice(&(max_len - 1).to_be_bytes());
+
+    let mut hasher = Sha256::import_state(&exported).expect("to import");
+    hasher.update(b"ab");
+}
+
+#[test]
+fn test_update_bits() {
+    //RFC 6234 section 8.5, TEST7 and TEST8 with extra bits
+    let tests: [(&[u8], usize, &str); 2] = [
+        (&[0x68], 5, "d6d3e02a31a84a8caa9718ed6c2057be09db45e7823eb5079ce7a573a3760f95"),
+        (&[0xbe, 0x27, 0x46, 0xc6, 0xdb, 0x52, 0x76, 0x5f, 0xdb, 0x2f, 0x88, 0x70, 0x0f, 0x9a, 0x73, 0x60], 123, "77ec1dc89c821ff2a1279089fa091b35b8cd960bcaf7de01c6a7680756beb972"),
+    ];
+
+    for (data, bit_len, expected) in tests.iter() {
+        let mut hasher = Sha256::new();
+        hasher.update_bits(data, *bit_len);
+        assert_eq!(digest_to_hex(hasher.result()), *expected);
+
+        let mut hasher = Sha256::new();
+        hasher.update(&data[..bit_len / 8]);
+        hasher.update_bits(&data[bit_len / 8..], bit_len % 8);
+        assert_eq!(digest_to_hex(hasher.result()), *expected);
+    }
+
+    //Unused low bits of partial byte are ignored
+    let mut hasher = Sha256::new();
+    hasher.update_bits(&[0x6F], 5);
+    assert_eq!(digest_to_hex(hasher.result()), tests[0].2);
+
+    let mut data = [0u8; 200];
+    for (idx, byte) in data.iter_mut().enumerate() {
+        *byte = idx as u8;
+    }
+    //Partial byte at end of block, cross-checked against bitwise reference implementation
+    let tests = [
+        (&data[1..65], 511, "13f890f21ae8fa1cb54070cb1e353f9c2ed070db8395ecccb486f6f57cea96da"),
+        (&data[1..65], 505, "c1a5c2c8d7da16a471895bc5f18f570071b7ca4d635eab307ef13cdd8c62a538"),
+        (&data[..], 1001, "f86e5e3b3932b27a7db56765c686e4a053d0136de25f98aa34a1f13bfb8950bb"),
+    ];
+
+    for (data, bit_len, expected) in tests.iter() {
+        let mut hasher = Sha256::new();
+        hasher.update(&data[..3]);
+        hasher.update_bits(&data[3..], bit_len - 24);
+        assert_eq!(digest_to_hex(hasher.result()), *expected);
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update_bits(&data[..3], 24);
+    assert_eq!(hasher.result(), lhash::sha256(&data[..3]));
+}
+
+#[test]
+#[should_panic(expected = "Input after partial byte")]
+fn test_update_after_partial_byte() {
+    let mut hasher = Sha256::new();
+    hasher.update_bits(&[0x98], 5);
+    hasher.update(b"a");
+}
+
+test_compress_blocks!(Sha256, Sha256State, lhash::sha256, (3u64 * 8).to_be_bytes());
+
+#[test]
+#[should_panic(expected = "Input after partial byte")]
+fn test_compress_blocks_after_partial_byte() {
+    let mut hasher = Sha256::new();
+    hasher.update_bits(&[0x80], 1);
+    hasher.compress_blocks(&[]);
+}
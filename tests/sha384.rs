@@ -0,0 +1,93 @@
+use lhash::{Digest, DigestFmt, Sha384, hmac};
+
+#[macro_use]
+mod common;
+
+fn digest_to_hex(input: impl AsRef<[u8]>) -> String {
+    DigestFmt(input).to_string()
+}
+
+#[test]
+fn test_simple() {
+    let tests = [
+        ("", "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b"),
+        ("abc", "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7"),
+        ("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "3391fdddfc8dc7393707a65b1b4709397cf8b1d162af05abfe8f450de5f36bc6b0455a8520bc4e6f5fe95b1fe3c8452b"),
+        ("abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu", "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712fcc7c71a557e2db966c3e9fa91746039"),
+    ];
+
+    let mut hasher = Sha384::new();
+    let mut chunked = Sha384::new();
+    for (data, expected) in tests.iter() {
+        let data = data.as_bytes();
+
+        let mut chunked_const = Sha384::new();
+        hasher.update(data);
+        for chunk in data.chunks(25) {
+            chunked.update(chunk);
+            chunked_const = chunked_const.const_update(chunk);
+        }
+
+        let hash = digest_to_hex(hasher.result());
+        let chunked_hash = digest_to_hex(chunked.result());
+        let const_hash = digest_to_hex(lhash::sha384(data));
+        let const_chunked_hash = digest_to_hex(chunked_const.const_result());
+
+        assert_eq!(const_hash.len(), hash.len());
+        assert_eq!(hash, *expected);
+        assert_eq!(const_hash, *expected);
+        assert_eq!(hash, chunked_hash);
+        assert_eq!(hash, const_chunked_hash);
+
+        hasher.reset();
+        chunked.reset();
+    }
+}
+
+#[test]
+fn test_hmac() {
+    let tests: [(&'static [u8], &'static [u8], &'static str); 5] = [
+        (&[0x0B; 20], b"Hi There", "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59cfaea9ea9076ede7f4af152e8b2fa9cb6"),
+        (b"Jefe", b"what do ya want for nothing?", "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649"),
+        (&[0xAA; 20], &[0xDD; 50], "88062608d3e6ad8a0aa2ace014c8a86f0aa635d947ac9febe83ef4e55966144b2a5ab39dc13814b94e3ab6e101a34f27"),
+        (&[0xAA; 131], b"Test Using Larger Than Block-Size Key - Hash Key First", "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c60c2ef6ab4030fe8296248df163f44952"),
+        (&[0xAA; 131], b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.", "6617178e941f020d351e2f254e8fd32c602420feb0b8fb9adccebb82461e99c5a678cc31e799176d3860e6110c46523e"),
+    ];
+
+    for (key, data, expected) in tests.iter() {
+        let hash = hmac::<Sha384>(data, key);
+        let hash = digest_to_hex(hash);
+
+        assert_eq!(hash, *expected);
+    }
+}
+
+//2.16.840.1.101.3.4.2.2
+test_metadata!(Sha384, 48, 128, "SHA-384", [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02]);
+
+test_finalize!(Sha384, lhash::sha384);
+
+test_traits!(Sha384, lhash::sha384);
+
+test_export_state!(Sha384, lhash::sha384, 210);
+
+#[test]
+fn test_custom_iv() {
+    //SHA-384 is SHA-512 compression with different initial value and truncated output
+    let mut state = lhash::Sha512State([
+        0xcbbb9d5dc1059ed8, 0x629a292a367cd507, 0x9159015a3070dd17, 0x152fecd8f70e5939,
+        0x67332667ffc00b31, 0x8eb44a8768581511, 0xdb0c2e0d64f98fa7, 0x47b5481dbefa4fa4,
+    ]);
+
+    let mut block = [0u8; 128];
+    block[..3].copy_from_slice(b"abc");
+    block[3] = 0x80;
+    block[127] = 3 * 8;
+    state.compress_blocks(&[block]);
+
+    assert_eq!(state.to_bytes()[..48], lhash::sha384(b"abc")[..]);
+
+    let mut hasher = Sha384::new();
+    hasher.compress_blocks(&[[0x61u8; 128]]);
+    assert_eq!(hasher.result(), lhash::sha384(&[0x61u8; 128]));
+}
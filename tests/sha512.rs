@@ -1,4 +1,7 @@
-use lhash::{DigestFmt, Sha512, hmac};
+use lhash::{Digest, DigestFmt, Sha512, Sha512State, StateError, hmac, hmac_sha512};
+
+#[macro_use]
+mod common;
 
 fn digest_to_hex(input: impl AsRef<[u8]>) -> String {
     crate::DigestFmt(input).to_string()
@@ -61,3 +64,126 @@ fn test_hmac() {
         assert_eq!(hash, *expected);
     }
 }
+
+#[test]
+fn test_const_hmac() {
+    const HASHES: [[u8; 64]; 4] = [
+        hmac_sha512(b"Hi There", &[0x0B; 20]),
+        hmac_sha512(b"what do ya want for nothing?", b"Jefe"),
+        hmac_sha512(&[0xDD; 50], &[0xAA; 20]),
+        hmac_sha512(b"Test Using Larger Than Block-Size Key - Hash Key First", &[0xAA; 131]),
+    ];
+    let expected = [
+        "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+        "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+        "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb",
+        "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+    ];
+
+    for (hash, expected) in HASHES.iter().zip(expected.iter()) {
+        assert_eq!(digest_to_hex(hash), *expected);
+    }
+}
+
+//2.16.840.1.101.3.4.2.3
+test_metadata!(Sha512, 64, 128, "SHA-512", [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03]);
+
+test_finalize!(Sha512, lhash::sha512);
+
+test_traits!(Sha512, lhash::sha512);
+
+test_export_state!(Sha512, lhash::sha512, 210);
+
+#[test]
+fn test_import_state_errors() {
+    let mut exported = Sha512::new().export_state();
+    exported[2] = 0x20;
+    assert_eq!(Sha512::import_state(&exported).unwrap_err(), StateError::InvalidLength);
+
+    let mut exported = Sha512::new().export_state();
+    exported[1] = 5;
+    assert_eq!(Sha512::import_state(&exported).unwrap_err(), StateError::AlgorithmMismatch);
+}
+
+#[test]
+fn test_length_limit() {
+    let max_len: u128 = (1 << 125) - 1;
+    let mut exported = Sha512::new().export_state();
+    exported[2..18].copy_from_slice(&(max_len - 1).to_be_bytes());
+
+    let mut hasher = Sha512::import_state(&exported).expect("to import");
+    hasher.update(b"a");
+    hasher.update(b"");
+    hasher.result();
+
+    exported[2..18].copy_from_slice(&(max_len + 1).to_be_bytes());
+    assert_eq!(Sha512::import_state(&exported).unwrap_err(), StateError::InvalidLength);
+}
+
+#[test]
+#[should_panic(expected = "Input length exceeds SHA-512 limit")]
+fn test_length_overflow() {
+    let max_len: u128 = (1 << 125) - 1;
+    let mut exported = Sha512::new().export_state();
+    exported[2..18].copy_from_slice(&(max_len - 1).to_be_bytes());
+
+    let mut hasher = Sha512::import_state(&exported).expect("to import");
+    hasher.update(b"ab");
+}
+
+#[test]
+fn test_update_bits() {
+    //RFC 6234 section 8.5, TEST7 and TEST8 with extra bits
+    let tests: [(&[u8], usize, &str); 2] = [
+        (&[0xb0], 5, "d4ee29a9e90985446b913cf1d1376c836f4be2c1cf3cada0720a6bf4857d886a7ecb3c4e4c0fa8c7f95214e41dc1b0d21b22a84cc03bf8ce4845f34dd5bdbad4"),
+        (&[0x08, 0xec, 0xb5, 0x2e, 0xba, 0xe1, 0xf7, 0x42, 0x2d, 0xb6, 0x2b, 0xcd, 0x54, 0x26, 0x70, 0x80], 123, "ed8dc78e8b01b69750053dbb7a0a9eda0fb9e9d292b1ed715e80a7fe290a4e16664fd913e85854400c5af05e6dad316b7359b43e64f8bec3c1f237119986bbb6"),
+    ];
+
+    for (data, bit_len, expected) in tests.iter() {
+        let mut hasher = Sha512::new();
+        hasher.update_bits(data, *bit_len);
+        assert_eq!(digest_to_hex(hasher.result()), *expected);
+
+        let mut hasher = Sha512::new();
+        hasher.update(&data[..bit_len / 8]);
+        hasher.update_bits(&data[bit_len / 8..], bit_len % 8);
+        assert_eq!(digest_to_hex(hasher.result()), *expected);
+    }
+
+    //Unused low bits of partial byte are ignored
+    let mut hasher = Sha512::new();
+    hasher.update_bits(&[0xB7], 5);
+    assert_eq!(digest_to_hex(hasher.result()), tests[0].2);
+
+    let mut data = [0u8; 200];
+    for (idx, byte) in data.iter_mut().enumerate() {
+        *byte = idx as u8;
+    }
+    //Partial byte at end of block, cross-checked against bitwise reference implementation
+    let tests = [
+        (&data[1..65], 511, "6f5fdeec49f052b1d51dc475cb70133c2fda656b9a5bafb75d011b044fccbb2dd769e632a3dedbc695669c7f6334dcf0e4f6ad880973e0cd70a94c1af0f0e682"),
+        (&data[1..65], 505, "84c6208681fefcec72336e62e4e0fc871bb5dc0512cdd3e43ff559f093e035e303b75c30dc93196e994b9a78a00dc92573a6c1591a73c7dbd1475306f81ca234"),
+        (&data[..], 1001, "b372c8107ad34359fe95103c0fdb674a39a2e9284f185952ddcc067d052612454c2a93c23feaac4db4ceadb74779dce2423e5243528bbc52dfec4120933e8417"),
+    ];
+
+    for (data, bit_len, expected) in tests.iter() {
+        let mut hasher = Sha512::new();
+        hasher.update(&data[..3]);
+        hasher.update_bits(&data[3..], bit_len - 24);
+        assert_eq!(digest_to_hex(hasher.result()), *expected);
+    }
+
+    let mut hasher = Sha512::new();
+    hasher.update_bits(&data[..3], 24);
+    assert_eq!(hasher.result(), lhash::sha512(&data[..3]));
+}
+
+#[test]
+#[should_panic(expected = "Input after partial byte")]
+fn test_update_after_partial_byte() {
+    let mut hasher = Sha512::new();
+    hasher.update_bits(&[0x98], 5);
+    hasher.update(b"a");
+}
+
+test_compress_blocks!(Sha512, Sha512State, lhash::sha512, (3u128 * 8).to_be_bytes());
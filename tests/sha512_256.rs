@@ -0,0 +1,72 @@
+use lhash::{Digest, DigestFmt, Sha512_256, hmac};
+
+#[macro_use]
+mod common;
+
+fn digest_to_hex(input: impl AsRef<[u8]>) -> String {
+    DigestFmt(input).to_string()
+}
+
+#[test]
+fn test_simple() {
+    let tests = [
+        ("", "c672b8d1ef56ed28ab87c3622c5114069bdd3ad7b8f9737498d0c01ecef0967a"),
+        ("abc", "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23"),
+        ("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "bde8e1f9f19bb9fd3406c90ec6bc47bd36d8ada9f11880dbc8a22a7078b6a461"),
+        ("abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu", "3928e184fb8690f840da3988121d31be65cb9d3ef83ee6146feac861e19b563a"),
+    ];
+
+    let mut hasher = Sha512_256::new();
+    let mut chunked = Sha512_256::new();
+    for (data, expected) in tests.iter() {
+        let data = data.as_bytes();
+
+        let mut chunked_const = Sha512_256::new();
+        hasher.update(data);
+        for chunk in data.chunks(25) {
+            chunked.update(chunk);
+            chunked_const = chunked_const.const_update(chunk);
+        }
+
+        let hash = digest_to_hex(hasher.result());
+        let chunked_hash = digest_to_hex(chunked.result());
+        let const_hash = digest_to_hex(lhash::sha512_256(data));
+        let const_chunked_hash = digest_to_hex(chunked_const.const_result());
+
+        assert_eq!(const_hash.len(), hash.len());
+        assert_eq!(hash, *expected);
+        assert_eq!(const_hash, *expected);
+        assert_eq!(hash, chunked_hash);
+        assert_eq!(hash, const_chunked_hash);
+
+        hasher.reset();
+        chunked.reset();
+    }
+}
+
+#[test]
+fn test_hmac() {
+    let tests: [(&'static [u8], &'static [u8], &'static str); 5] = [
+        (&[0x0B; 20], b"Hi There", "9f9126c3d9c3c330d760425ca8a217e31feae31bfe70196ff81642b868402eab"),
+        (b"Jefe", b"what do ya want for nothing?", "6df7b24630d5ccb2ee335407081a87188c221489768fa2020513b2d593359456"),
+        (&[0xAA; 20], &[0xDD; 50], "229006391d66c8ecddf43ba5cf8f83530ef221a4e9401840d1bead5137c8a2ea"),
+        (&[0xAA; 131], b"Test Using Larger Than Block-Size Key - Hash Key First", "87123c45f7c537a404f8f47cdbedda1fc9bec60eeb971982ce7ef10e774e6539"),
+        (&[0xAA; 131], b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.", "6ea83f8e7315072c0bdaa33b93a26fc1659974637a9db8a887d06c05a7f35a66"),
+    ];
+
+    for (key, data, expected) in tests.iter() {
+        let hash = hmac::<Sha512_256>(data, key);
+        let hash = digest_to_hex(hash);
+
+        assert_eq!(hash, *expected);
+    }
+}
+
+//2.16.840.1.101.3.4.2.6
+test_metadata!(Sha512_256, 32, 128, "SHA-512/256", [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x06]);
+
+test_finalize!(Sha512_256, lhash::sha512_256);
+
+test_traits!(Sha512_256, lhash::sha512_256);
+
+test_export_state!(Sha512_256, lhash::sha512_256, 210);
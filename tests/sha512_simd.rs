@@ -0,0 +1,55 @@
+#![cfg(target_arch = "x86_64")]
+
+use lhash::{sha384, sha512, sha512_256, Sha384, Sha512, Sha512State, Sha512_256};
+
+//All ones words carry across 32 bit halves and lanes in SIMD additions.
+const FILLS: [u8; 3] = [0x00, 0xFF, 0x80];
+
+#[test]
+fn test_block_boundaries() {
+    for fill in FILLS.iter() {
+        let input = [*fill; 4 * 128 + 1];
+        for len in [0, 1, 111, 112, 127, 128, 129, 239, 240, 255, 256, 257, 4 * 128, 4 * 128 + 1].iter() {
+            let input = &input[..*len];
+            let mut hasher = Sha512::new();
+            hasher.update(input);
+            assert_eq!(hasher.result(), sha512(input), "fill={:#x} len={}", fill, len);
+        }
+    }
+}
+
+#[test]
+fn test_buffered_then_blocks() {
+    let mut input = [0u8; 8 * 128 + 3];
+    for (idx, byte) in input.iter_mut().enumerate() {
+        *byte = (idx as u8).wrapping_mul(0x9D) ^ 0x5A;
+    }
+
+    //Partial buffer is completed, then remaining whole blocks are compressed in single call.
+    for split in [1, 64, 127].iter() {
+        let mut hasher = Sha512::new();
+        hasher.update(&input[..*split]);
+        hasher.update(&input[*split..]);
+        assert_eq!(hasher.result(), sha512(&input), "split={}", split);
+    }
+
+    let mut hasher = Sha384::new();
+    hasher.update(&input[..5]);
+    hasher.update(&input[5..]);
+    assert_eq!(hasher.result(), sha384(&input));
+
+    let mut hasher = Sha512_256::new();
+    hasher.update(&input[..5]);
+    hasher.update(&input[5..]);
+    assert_eq!(hasher.result(), sha512_256(&input));
+}
+
+#[test]
+fn test_compress_blocks() {
+    for fill in FILLS.iter() {
+        let blocks = [[*fill; 128]; 3];
+        let mut state = Sha512State::new();
+        state.compress_blocks(&blocks);
+        assert_eq!(state, blocks.iter().fold(Sha512State::new(), |state, block| state.compress(block)), "fill={:#x}", fill);
+    }
+}
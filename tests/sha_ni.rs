@@ -0,0 +1,73 @@
+#![cfg(target_arch = "x86_64")]
+
+use lhash::{sha1, sha256, Sha1, Sha1State, Sha256, Sha256State};
+
+//Deterministic filler, so failures are reproducible.
+fn data(len: usize) -> Vec<u8> {
+    let mut seed = 0x2545F491u32;
+    (0..len).map(|_| {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed as u8
+    }).collect()
+}
+
+#[test]
+fn test_sha1_matches_portable() {
+    let input = data(1024);
+
+    for len in 0..input.len() {
+        let input = &input[..len];
+        let mut hasher = Sha1::new();
+        hasher.update(input);
+        assert_eq!(hasher.result(), sha1(input), "len={}", len);
+        assert_eq!(hasher.result(), Sha1::new().const_update(input).const_result(), "len={}", len);
+    }
+
+    for chunk in [1, 3, 63, 64, 65, 127, 200].iter() {
+        let mut hasher = Sha1::new();
+        for part in input.chunks(*chunk) {
+            hasher.update(part);
+        }
+        assert_eq!(hasher.result(), sha1(&input), "chunk={}", chunk);
+    }
+}
+
+#[test]
+fn test_sha256_matches_portable() {
+    let input = data(1024);
+
+    for len in 0..input.len() {
+        let input = &input[..len];
+        let mut hasher = Sha256::new();
+        hasher.update(input);
+        assert_eq!(hasher.result(), sha256(input), "len={}", len);
+        assert_eq!(hasher.result(), Sha256::new().const_update(input).const_result(), "len={}", len);
+    }
+
+    for chunk in [1, 3, 63, 64, 65, 127, 200].iter() {
+        let mut hasher = Sha256::new();
+        for part in input.chunks(*chunk) {
+            hasher.update(part);
+        }
+        assert_eq!(hasher.result(), sha256(&input), "chunk={}", chunk);
+    }
+}
+
+#[test]
+fn test_compress_blocks_matches_portable() {
+    let input = data(64 * 16);
+    let mut blocks = [[0u8; 64]; 16];
+    for (block, chunk) in blocks.iter_mut().zip(input.chunks(64)) {
+        block.copy_from_slice(chunk);
+    }
+
+    let mut state = Sha1State::new();
+    state.compress_blocks(&blocks);
+    assert_eq!(state, blocks.iter().fold(Sha1State::new(), |state, block| state.compress(block)));
+
+    let mut state = Sha256State::new();
+    state.compress_blocks(&blocks);
+    assert_eq!(state, blocks.iter().fold(Sha256State::new(), |state, block| state.compress(block)));
+}
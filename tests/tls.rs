@@ -0,0 +1,59 @@
+use lhash::{DigestFmt, Sha256, Sha384, hmac};
+use lhash::tls::{prf, prf_legacy, hkdf_expand_label, derive_secret};
+
+mod common;
+use common::from_hex;
+
+fn digest_to_hex(input: impl AsRef<[u8]>) -> String {
+    DigestFmt(input).to_string()
+}
+
+#[test]
+fn test_prf() {
+    //TLS 1.2 PRF test vectors for P_SHA256 and P_SHA384, published on IETF TLS mailing list
+    let mut out = [0u8; 100];
+    prf::<Sha256>(&from_hex("9bbe436ba940f017b17652849a71db35"), b"test label", &from_hex("a0ba9f936cda311827a6f796ffd5198c"), &mut out);
+    assert_eq!(digest_to_hex(out), "e3f229ba727be17b8d122620557cd453c2aab21d07c3d495329b52d4e61edb5a6b301791e90d35c9c9a46b4e14baf9af0fa022f7077def17abfd3797c0564bab4fbc91666e9def9b97fce34f796789baa48082d122ee42c5a72e5a5110fff70187347b66");
+
+    let mut out = [0u8; 148];
+    prf::<Sha384>(&from_hex("b80b733d6ceefcdc71566ea48e5567df"), b"test label", &from_hex("cd665cf6a8447dd6ff8b27555edb7465"), &mut out);
+    assert_eq!(digest_to_hex(&out[..]), "7b0c18e9ced410ed1804f2cfa34a336a1c14dffb4900bb5fd7942107e81c83cde9ca0faa60be9fe34f82b1233c9146a0e534cb400fed2700884f9dc236f80edd8bfa961144c9e8d792eca722a7b32fc3d416d473ebc2c5fd4abfdad05d9184259b5bf8cd4d90fa0d31e2dec479e4f1a26066f2eea9a69236a3e52655c9e9aee691c8f3a26854308d5eaa3be85e0990703d73e56f");
+}
+
+#[test]
+fn test_prf_legacy() {
+    //Expected values are output of OpenSSL 3.5 TLS1-PRF with MD5-SHA1 digest for same inputs
+    let seed = (100..164).collect::<Vec<u8>>();
+
+    let mut out = [0u8; 104];
+    let secret = (0..48).collect::<Vec<u8>>();
+    prf_legacy(&secret, b"master secret", &seed, &mut out);
+    assert_eq!(digest_to_hex(&out[..]), "fb1618751056ceb62e4ddb170ea8f4a5b2c99024798ceee10ee10b6f2f0d78ba3a80453f30052265edea9f8d8f5d2303a2d7c89c05284c79f1f338649dbd980ec409b56ced02e94ff2d74d90fd88b0c8fb1c06f11e4bdfb20af1e2694621269f20284da47d4399b3");
+
+    //Odd secret length shares middle byte between halves
+    let mut out = [0u8; 48];
+    let secret = (0..47).collect::<Vec<u8>>();
+    prf_legacy(&secret, b"master secret", &seed, &mut out);
+    assert_eq!(digest_to_hex(&out[..]), "9f56ae042bd31a4482cf24d6976c31fec326266662d5aa49976004138a36ae7ce2680a5eea1f93bffe9206cb1c8be4a5");
+}
+
+#[test]
+fn test_tls13_key_schedule() {
+    //RFC 8448 Simple 1-RTT Handshake
+    let early_secret = hmac::<Sha256>(&[0; 32], &[]);
+    assert_eq!(digest_to_hex(early_secret), "33ad0a1c607ec03b09e6cd9893680ce210adf300aa1f2660e1b22e10f170f92a");
+
+    let derived = derive_secret::<Sha256>(&early_secret, b"derived", &lhash::sha256(b""));
+    assert_eq!(digest_to_hex(derived), "6f2615a108c702c5678f54fc9dbab69716c076189c48250cebeac3576c3611ba");
+
+    let handshake_secret = hmac::<Sha256>(&from_hex("8bd4054fb55b9d63fdfbacf9f04b9f0d35e6d63f537563efd46272900f89492d"), &derived);
+    assert_eq!(digest_to_hex(handshake_secret), "1dc826e93606aa6fdc0aadc12f741b01046aa6b99f691ed221a9f0ca043fbeac");
+
+    let server_handshake_secret = from_hex("b67b7d690cc16c4e75e54213cb2d37b4e9c912bcded9105d42befd59d391ad38");
+    let mut key = [0u8; 16];
+    hkdf_expand_label::<Sha256>(&server_handshake_secret, b"key", &[], &mut key);
+    assert_eq!(digest_to_hex(key), "3fce516009c21727d0f2e4e86ee403bc");
+    let mut iv = [0u8; 12];
+    hkdf_expand_label::<Sha256>(&server_handshake_secret, b"iv", &[], &mut iv);
+    assert_eq!(digest_to_hex(iv), "5d313eb2671276ee13000b30");
+}
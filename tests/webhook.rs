@@ -0,0 +1,70 @@
+use lhash::webhook::{verify_github, verify_slack, verify_stripe, WebhookError, DEFAULT_TOLERANCE};
+
+#[test]
+fn test_github() {
+    //GitHub documentation example
+    let secret = b"It's a Secret to Everybody";
+    let body = b"Hello, World!";
+
+    assert_eq!(verify_github(secret, "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17", body), Ok(()));
+    assert_eq!(verify_github(secret, "sha256=757107EA0EB2509FC211221CCE984B8A37570B6D7586C22C46F4379C8B043E17", body), Ok(()));
+    assert_eq!(verify_github(secret, "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17", b"Hello, World"), Err(WebhookError::InvalidSignature));
+    assert_eq!(verify_github(b"secret", "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17", body), Err(WebhookError::InvalidSignature));
+    assert_eq!(verify_github(secret, "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e1", body), Err(WebhookError::InvalidHeader));
+    assert_eq!(verify_github(secret, "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e1z", body), Err(WebhookError::InvalidHeader));
+    assert_eq!(verify_github(secret, "sha256=+57107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17", body), Err(WebhookError::InvalidHeader));
+    assert_eq!(verify_github(secret, "757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17", body), Err(WebhookError::InvalidHeader));
+    //Non-ASCII header of correct byte length.
+    assert_eq!(verify_github(secret, &format!("sha256={}", "é".repeat(32)), body), Err(WebhookError::InvalidHeader));
+    assert_eq!(verify_github(secret, "sha256=757107EA0EB2509FC211221CCE984B8A37570B6D7586C22C46F4379C8B043E17", body), Ok(()));
+
+    assert_eq!(verify_github(secret, "sha1=01dc10d0c83e72ed246219cdd91669667fe2ca59", body), Ok(()));
+    assert_eq!(verify_github(secret, "sha1=01dc10d0c83e72ed246219cdd91669667fe2ca58", body), Err(WebhookError::InvalidSignature));
+}
+
+#[test]
+fn test_stripe() {
+    let secret = b"whsec_test";
+    let body = br#"{"id":"evt_test"}"#;
+    let signature = "91756ee38f5c256c6db5893fecc447d8729adb8023535a860e4c2b521391cec9";
+    let header = format!("t=1492774577,v1={},v0=6ffbb59b2300aae63f272406069a9788598b792a944a07aba816edb039989a39", signature);
+
+    assert_eq!(verify_stripe(secret, &header, body, 1492774577, DEFAULT_TOLERANCE), Ok(()));
+    assert_eq!(verify_stripe(secret, &header, body, 1492774577 + 300, DEFAULT_TOLERANCE), Ok(()));
+    assert_eq!(verify_stripe(secret, &header, body, 1492774577 - 300, DEFAULT_TOLERANCE), Ok(()));
+    assert_eq!(verify_stripe(secret, &header, body, 1492774577 + 301, DEFAULT_TOLERANCE), Err(WebhookError::TimestampOutOfRange));
+    assert_eq!(verify_stripe(secret, &header, body, 1492774577 - 301, DEFAULT_TOLERANCE), Err(WebhookError::TimestampOutOfRange));
+    assert_eq!(verify_stripe(secret, &header, b"{}", 1492774577, DEFAULT_TOLERANCE), Err(WebhookError::InvalidSignature));
+
+    //Any of multiple signatures, e.g. during secret rotation
+    let rotated = format!("t=1492774577,v1=0000000000000000000000000000000000000000000000000000000000000000, v1={}", signature);
+    assert_eq!(verify_stripe(secret, &rotated, body, 1492774577, DEFAULT_TOLERANCE), Ok(()));
+    let rotated = format!("t=1492774577,v1=00,v1={}", signature);
+    assert_eq!(verify_stripe(secret, &rotated, body, 1492774577, DEFAULT_TOLERANCE), Ok(()));
+
+    let tampered = format!("t=1492774578,v1={}", signature);
+    assert_eq!(verify_stripe(secret, &tampered, body, 1492774577, DEFAULT_TOLERANCE), Err(WebhookError::InvalidSignature));
+    let invalid = format!("v1={}", signature);
+    assert_eq!(verify_stripe(secret, &invalid, body, 1492774577, DEFAULT_TOLERANCE), Err(WebhookError::InvalidHeader));
+    let invalid = format!("t=1492774577,t=1492774577,v1={}", signature);
+    assert_eq!(verify_stripe(secret, &invalid, body, 1492774577, DEFAULT_TOLERANCE), Err(WebhookError::InvalidHeader));
+    assert_eq!(verify_stripe(secret, "t=-1,v1=00", body, 1492774577, DEFAULT_TOLERANCE), Err(WebhookError::InvalidHeader));
+    assert_eq!(verify_stripe(secret, "t=1492774577,v0=00", body, 1492774577, DEFAULT_TOLERANCE), Err(WebhookError::InvalidHeader));
+}
+
+#[test]
+fn test_slack() {
+    //Slack documentation example
+    let secret = b"8f742231b10e8888abcd99yyyzzz85a5";
+    let body = b"token=xyzz0WbapA4vBCDEFasx0q6G&team_id=T1DC2JH3J&team_domain=testteamnow&channel_id=G8PSS9T3V&channel_name=foobar&user_id=U2CERLKJA&user_name=roadrunner&command=%2Fwebhook-collect&text=&response_url=https%3A%2F%2Fhooks.slack.com%2Fcommands%2FT1DC2JH3J%2F397700885554%2F96rGlfmibIGlgcZRskXaIFfN&trigger_id=398738663015.47445629121.803a0bc887a14d10d2c447fce8b6703c";
+    let signature = "v0=a2114d57b48eac39b9ad189dd8316235a7b4a8d21a10bd27519666489c69b503";
+
+    assert_eq!(verify_slack(secret, "1531420618", signature, body, 1531420618, DEFAULT_TOLERANCE), Ok(()));
+    assert_eq!(verify_slack(secret, "1531420618", signature, body, 1531420618 + 60, 60), Ok(()));
+    assert_eq!(verify_slack(secret, "1531420618", signature, body, 1531420618 + 61, 60), Err(WebhookError::TimestampOutOfRange));
+    assert_eq!(verify_slack(secret, "1531420619", signature, body, 1531420618, DEFAULT_TOLERANCE), Err(WebhookError::InvalidSignature));
+    assert_eq!(verify_slack(secret, "1531420618", signature, &body[1..], 1531420618, DEFAULT_TOLERANCE), Err(WebhookError::InvalidSignature));
+    assert_eq!(verify_slack(secret, "1531420618", &signature[3..], body, 1531420618, DEFAULT_TOLERANCE), Err(WebhookError::InvalidHeader));
+    assert_eq!(verify_slack(secret, "", signature, body, 1531420618, DEFAULT_TOLERANCE), Err(WebhookError::InvalidHeader));
+    assert_eq!(verify_slack(secret, "99999999999999999999999", signature, body, 1531420618, DEFAULT_TOLERANCE), Err(WebhookError::InvalidHeader));
+}